@@ -111,8 +111,57 @@ impl<'w> Benchmark<'w> {
         }
         self.2 = output;
     }
+
+    pub fn run_dense(&mut self, query: &mut QueryState<DenseAll<&'w dyn Messages>>) {
+        let mut output = Vec::new();
+        for all in query.iter_mut(&mut self.0) {
+            for x in all {
+                output.push(x.messages().len());
+            }
+        }
+        self.2 = output;
+    }
+}
+
+macro_rules! many_impls {
+    ($( $name:ident ),*) => {
+        $(
+            #[derive(Component)]
+            pub struct $name {
+                messages: Vec<String>,
+            }
+
+            impl Messages for $name {
+                fn messages(&self) -> &[String] {
+                    &self.messages
+                }
+                fn send_message(&mut self, msg: &dyn Display) {
+                    self.messages.push(msg.to_string());
+                }
+            }
+        )*
+
+        impl<'w> Benchmark<'w> {
+            // Many impls are registered, but each entity only has one of them.
+            fn many_impls() -> Self {
+                let mut world = World::new();
+
+                $(
+                    world.register_component_as::<dyn Messages, $name>();
+                    for _ in 0..625 {
+                        world.spawn((Name::new("Hello"), $name { messages: vec![] }));
+                    }
+                )*
+
+                let query = world.query();
+                Self(world, query, default())
+            }
+        }
+    };
 }
 
+many_impls!(M0, M1, M2, M3, M4, M5, M6, M7, M8, M9, M10, M11, M12, M13, M14, M15);
+
 pub fn one(c: &mut Criterion) {
     let mut benchmark = Benchmark::one();
     c.bench_function("All<> - 1 match", |b| b.iter(|| benchmark.run()));
@@ -129,5 +178,45 @@ pub fn distributed(c: &mut Criterion) {
     eprintln!("{}", benchmark.2.len());
 }
 
-criterion_group!(all, one, multiple, distributed);
+pub fn one_dense(c: &mut Criterion) {
+    let mut benchmark = Benchmark::one();
+    let mut query = benchmark.0.query();
+    c.bench_function("DenseAll<> - 1 match", |b| {
+        b.iter(|| benchmark.run_dense(&mut query))
+    });
+    eprintln!("{}", benchmark.2.len());
+}
+pub fn multiple_dense(c: &mut Criterion) {
+    let mut benchmark = Benchmark::multiple();
+    let mut query = benchmark.0.query();
+    c.bench_function("DenseAll<> - 2 matches", |b| {
+        b.iter(|| benchmark.run_dense(&mut query))
+    });
+    eprintln!("{}", benchmark.2.len());
+}
+pub fn distributed_dense(c: &mut Criterion) {
+    let mut benchmark = Benchmark::distributed();
+    let mut query = benchmark.0.query();
+    c.bench_function("DenseAll<> - 1-2 matches", |b| {
+        b.iter(|| benchmark.run_dense(&mut query))
+    });
+    eprintln!("{}", benchmark.2.len());
+}
+
+pub fn many_impls(c: &mut Criterion) {
+    let mut benchmark = Benchmark::many_impls();
+    c.bench_function("All<> - 1 of 16 impls", |b| b.iter(|| benchmark.run()));
+    eprintln!("{}", benchmark.2.len());
+}
+
+criterion_group!(
+    all,
+    one,
+    multiple,
+    distributed,
+    many_impls,
+    one_dense,
+    multiple_dense,
+    distributed_dense
+);
 criterion_main!(all);
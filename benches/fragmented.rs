@@ -44,6 +44,7 @@ macro_rules! create_entities {
     ($world:ident; $( $variants:ident ),*) => {
         $(
             #[derive(Component)]
+            #[allow(dead_code)]
             struct $variants(f32);
             for _ in 0..20 {
                 $world.spawn(($variants(0.0), RecA { messages: vec![] }, RecB { messages: vec![] }));
@@ -95,6 +96,19 @@ pub fn one(c: &mut Criterion) {
     });
     eprintln!("{}", output.len());
 }
+pub fn dense_one(c: &mut Criterion) {
+    let mut benchmark = Benchmark::new();
+    let mut query = benchmark.0.query::<DenseOne<&mut dyn Messages>>();
+    let mut output = Vec::new();
+    c.bench_function("DenseOne<> - fragmented", |b| {
+        b.iter(|| {
+            for x in query.iter_mut(&mut benchmark.0) {
+                output.push(x.messages().len());
+            }
+        });
+    });
+    eprintln!("{}", output.len());
+}
 pub fn all(c: &mut Criterion) {
     let mut benchmark = Benchmark::new();
     let mut query = benchmark.0.query::<&mut dyn Messages>();
@@ -111,5 +125,21 @@ pub fn all(c: &mut Criterion) {
     eprintln!("{}", output.len());
 }
 
-criterion_group!(fragmented, concrete, one, all);
+pub fn dense_all(c: &mut Criterion) {
+    let mut benchmark = Benchmark::new();
+    let mut query = benchmark.0.query::<DenseAll<&mut dyn Messages>>();
+    let mut output = Vec::new();
+    c.bench_function("DenseAll<> - fragmented", |b| {
+        b.iter(|| {
+            for all in query.iter_mut(&mut benchmark.0) {
+                for x in all {
+                    output.push(x.messages().len());
+                }
+            }
+        });
+    });
+    eprintln!("{}", output.len());
+}
+
+criterion_group!(fragmented, concrete, one, dense_one, all, dense_all);
 criterion_main!(fragmented);
@@ -92,6 +92,14 @@ impl<'w> Benchmark<'w> {
         }
         self.2 = output;
     }
+
+    pub fn run_dense(&mut self, query: &mut QueryState<DenseOne<&'w dyn Messages>>) {
+        let mut output = Vec::new();
+        for x in query.iter_mut(&mut self.0) {
+            output.push(x.messages().len());
+        }
+        self.2 = output;
+    }
 }
 
 pub fn one_match(c: &mut Criterion) {
@@ -105,5 +113,20 @@ pub fn filtering(c: &mut Criterion) {
     eprintln!("{}", benchmark.2.len());
 }
 
-criterion_group!(one, one_match, filtering);
+pub fn one_match_dense(c: &mut Criterion) {
+    let mut benchmark = Benchmark::one();
+    let mut query = benchmark.0.query();
+    c.bench_function("DenseOne<>", |b| b.iter(|| benchmark.run_dense(&mut query)));
+    eprintln!("{}", benchmark.2.len());
+}
+pub fn filtering_dense(c: &mut Criterion) {
+    let mut benchmark = Benchmark::filtered();
+    let mut query = benchmark.0.query();
+    c.bench_function("DenseOne<> - filtering", |b| {
+        b.iter(|| benchmark.run_dense(&mut query))
+    });
+    eprintln!("{}", benchmark.2.len());
+}
+
+criterion_group!(one, one_match, filtering, one_match_dense, filtering_dense);
 criterion_main!(one);
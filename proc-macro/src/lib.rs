@@ -30,6 +30,15 @@ use syn::{parse_quote, ItemTrait, Result, TraitItem};
 ///
 /// You may opt out of this by using the form `#[queryable(no_bounds)]`,
 /// but you will have to add the bounds yourself to make it compile.
+///
+/// # Reflection
+///
+/// Using the form `#[queryable(reflect)]` also generates a type alias named `Reflect{Trait}`
+/// for `ReflectTraitQuery<dyn Trait>`, which can be used as type data with `#[reflect(Trait)]`.
+/// Components with this type data can be registered all at once with
+/// `register_traits_from_type_registry`. This is not supported for generic traits.
+///
+/// Multiple options can be combined, as in `#[queryable(no_bounds, reflect)]`.
 #[proc_macro_attribute]
 pub fn queryable(attr: TokenStream, item: TokenStream) -> TokenStream {
     impl_trait_query(attr, item)
@@ -38,19 +47,39 @@ pub fn queryable(attr: TokenStream, item: TokenStream) -> TokenStream {
 }
 
 fn impl_trait_query(arg: TokenStream, item: TokenStream) -> Result<TokenStream2> {
-    syn::custom_keyword!(no_bounds);
-    let no_bounds: Option<no_bounds> = syn::parse(arg).map_err(|e| {
-        syn::Error::new(
-            e.span(),
-            "Valid forms are: `#[queryable]` and `#[queryable(no_bounds)]`",
-        )
-    })?;
+    let mut no_bounds = false;
+    let mut reflect = false;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("no_bounds") {
+            no_bounds = true;
+            Ok(())
+        } else if meta.path.is_ident("reflect") {
+            reflect = true;
+            Ok(())
+        } else {
+            Err(meta.error(
+                "Valid forms are: `#[queryable]`, `#[queryable(no_bounds)]` and `#[queryable(reflect)]`",
+            ))
+        }
+    });
+    syn::parse::Parser::parse(parser, arg)?;
 
     let mut trait_definition = syn::parse::<ItemTrait>(item)?;
     let trait_name = trait_definition.ident.clone();
 
+    let has_assoc_types = trait_definition
+        .items
+        .iter()
+        .any(|item| matches!(item, TraitItem::Type(_)));
+    if reflect && (!trait_definition.generics.params.is_empty() || has_assoc_types) {
+        return Err(syn::Error::new(
+            trait_name.span(),
+            "`#[queryable(reflect)]` is not supported for generic traits",
+        ));
+    }
+
     // Add `'static` bounds, unless the user asked us not to.
-    if !no_bounds.is_some() {
+    if !no_bounds {
         trait_definition.supertraits.push(parse_quote!('static));
 
         for param in &mut trait_definition.generics.params {
@@ -370,12 +399,29 @@ fn impl_trait_query(arg: TokenStream, item: TokenStream) -> Result<TokenStream2>
         }
     };
 
+    let reflect_code = if reflect {
+        let vis = &trait_definition.vis;
+        let reflect_name = format_ident!("Reflect{trait_name}");
+        let doc = format!(
+            "Type data for registering components with trait queries for `dyn {trait_name}`. \
+            See `bevy_trait_query::ReflectTraitQuery`."
+        );
+        quote! {
+            #[doc = #doc]
+            #vis type #reflect_name = #my_crate::ReflectTraitQuery<#trait_object>;
+        }
+    } else {
+        quote! {}
+    };
+
     Ok(quote! {
         #trait_definition
 
         #marker_impl_code
 
         #trait_object_query_code
+
+        #reflect_code
     })
 }
 
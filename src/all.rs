@@ -1,110 +1,463 @@
 use crate::{
-    debug_unreachable, trait_registry_error, zip_exact, TraitImplMeta, TraitImplRegistry,
-    TraitQuery, TraitQueryState,
+    debug_unreachable, trait_registry_error, ImplLists, TraitImplInfo, TraitImplMeta,
+    TraitImplRegistry, TraitQuery, TraitQueryState,
+};
+use bevy::ecs::archetype::Archetype;
+use bevy::ecs::change_detection::{Mut, Ref};
+use bevy::ecs::component::{
+    Component, ComponentId, ComponentStorage, StorageType, Tick, TickCells,
 };
-use bevy::ecs::change_detection::Mut;
-use bevy::ecs::component::{ComponentId, Tick};
 use bevy::ecs::entity::Entity;
 use bevy::ecs::query::{QueryItem, ReadOnlyWorldQuery, WorldQuery};
-use bevy::ecs::storage::{SparseSets, Table, TableRow};
+use bevy::ecs::storage::{Column, ComponentSparseSet, SparseSets, Table, TableRow};
 use bevy::ecs::world::unsafe_world_cell::UnsafeWorldCell;
 use bevy::ecs::world::World;
-use bevy::ptr::UnsafeCellDeref;
+use bevy::ptr::{Ptr, UnsafeCellDeref};
+use std::cell::UnsafeCell;
+use std::marker::PhantomData;
+
+/// Determines which trait impls get yielded when iterating over an entity's components,
+/// based on the change ticks of each component.
+#[derive(Clone, Copy)]
+enum TickFilter {
+    /// Yield every trait impl.
+    None,
+    /// Only yield trait impls that have been added since the system last ran.
+    Added,
+    /// Only yield trait impls that have changed since the system last ran.
+    Changed,
+}
+
+impl TickFilter {
+    /// SAFETY: The caller must have read access to the ticks of the component.
+    #[inline]
+    unsafe fn matches(
+        self,
+        added: &UnsafeCell<Tick>,
+        changed: &UnsafeCell<Tick>,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> bool {
+        match self {
+            Self::None => true,
+            Self::Added => added.read().is_newer_than(last_run, this_run),
+            Self::Changed => changed.read().is_newer_than(last_run, this_run),
+        }
+    }
+}
+
+/// How many of the impls present in a table or archetype have their storage cached.
+/// If more impls than this are present, each registered impl gets looked up for every entity instead.
+///
+/// The cache is copied into every query item, so this is kept small: most entities have one or two impls,
+/// and larger items make iterating over `All` noticeably slower.
+const CACHED_IMPLS: usize = 2;
+
+/// The storage of a registered trait impl that is present in the current table or archetype.
+#[derive(Clone, Copy)]
+enum ImplStorage<'w> {
+    /// `index` is the position of the impl in the registry.
+    Table { column: &'w Column, index: u32 },
+    SparseSet {
+        components: &'w ComponentSparseSet,
+        index: u32,
+    },
+}
+
+impl ImplStorage<'_> {
+    #[inline]
+    fn index(self) -> usize {
+        match self {
+            Self::Table { index, .. } | Self::SparseSet { index, .. } => index as usize,
+        }
+    }
+}
+
+/// The registered trait impls that are present in the current table or archetype.
+///
+/// This gets computed once in `set_archetype`/`set_table`, so that iterating over
+/// the impls for each entity only needs to look at the components that actually exist.
+#[derive(Clone, Copy)]
+enum PresentImpls<'w> {
+    /// The storage of each present impl in priority order, followed by `None`.
+    Cached([Option<ImplStorage<'w>>; CACHED_IMPLS]),
+    /// Too many impls are present to be cached.
+    Uncached,
+}
+
+impl<'w> PresentImpls<'w> {
+    /// No impls are present.
+    const NONE: Self = Self::Cached([None; CACHED_IMPLS]);
+
+    /// Finds the registered impls that are present in `archetype`, whose components are stored in `table`.
+    #[inline]
+    fn in_archetype<Trait: ?Sized>(
+        registry: &ImplLists<Trait>,
+        archetype: &Archetype,
+        table: &'w Table,
+        sparse_sets: &'w SparseSets,
+    ) -> Self {
+        Self::new(
+            registry,
+            |component, storage_type, index| match storage_type {
+                StorageType::Table => {
+                    (table.get_column(component)).map(|column| ImplStorage::Table { column, index })
+                }
+                StorageType::SparseSet if archetype.contains(component) => (sparse_sets
+                    .get(component))
+                .map(|components| ImplStorage::SparseSet { components, index }),
+                StorageType::SparseSet => None,
+            },
+        )
+    }
+
+    /// Finds the registered impls that are present in `table`.
+    /// This is only used by dense queries, which don't allow sparse set impls.
+    #[inline]
+    fn in_table<Trait: ?Sized>(registry: &ImplLists<Trait>, table: &'w Table) -> Self {
+        Self::new(
+            registry,
+            |component, storage_type, index| match storage_type {
+                StorageType::Table => {
+                    (table.get_column(component)).map(|column| ImplStorage::Table { column, index })
+                }
+                StorageType::SparseSet => None,
+            },
+        )
+    }
+
+    #[inline]
+    fn new<Trait: ?Sized>(
+        registry: &ImplLists<Trait>,
+        mut storage: impl FnMut(ComponentId, StorageType, u32) -> Option<ImplStorage<'w>>,
+    ) -> Self {
+        let mut impls = [None; CACHED_IMPLS];
+        let mut len = 0;
+        let registered = std::iter::zip(&registry.components, &registry.meta);
+        for (index, (&component, meta)) in registered.enumerate() {
+            let Some(storage) = storage(component, meta.storage_type, index as u32) else {
+                continue;
+            };
+            if len == CACHED_IMPLS {
+                return Self::Uncached;
+            }
+            impls[len] = Some(storage);
+            len += 1;
+        }
+        Self::Cached(impls)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::Cached([None, ..]))
+    }
+}
+
+/// Iterator over the components implementing a trait for a single entity, in priority order.
+#[derive(Clone, Copy)]
+struct EntityImplsIter<'a, Trait: ?Sized> {
+    registry: &'a ImplLists<Trait>,
+    impls: PresentImpls<'a>,
+    /// The index of the next impl to check,
+    /// within `impls` if it is cached or within `registry` otherwise.
+    next: usize,
+    table: &'a Table,
+    table_row: TableRow,
+    entity: Entity,
+    sparse_sets: &'a SparseSets,
+    filter: TickFilter,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+impl<'a, Trait: ?Sized> EntityImplsIter<'a, Trait> {
+    /// Returns the storage of the next impl that may exist for the entity.
+    #[inline]
+    fn next_storage(&mut self) -> Option<ImplStorage<'a>> {
+        match &self.impls {
+            PresentImpls::Cached(impls) => {
+                let storage = (*impls.get(self.next)?)?;
+                self.next += 1;
+                Some(storage)
+            }
+            // Look up the remaining registered impls until we find one that exists in this entity's storage.
+            PresentImpls::Uncached => loop {
+                let index = self.next;
+                let component = *self.registry.components.get(index)?;
+                self.next += 1;
+                let storage = match self.registry.meta[index].storage_type {
+                    StorageType::Table => {
+                        (self.table.get_column(component)).map(|column| ImplStorage::Table {
+                            column,
+                            index: index as u32,
+                        })
+                    }
+                    StorageType::SparseSet => {
+                        (self.sparse_sets.get(component)).map(|components| ImplStorage::SparseSet {
+                            components,
+                            index: index as u32,
+                        })
+                    }
+                };
+                if storage.is_some() {
+                    return storage;
+                }
+            },
+        }
+    }
+
+    /// Returns the next component that the entity has and which passes the filter.
+    ///
+    /// # Safety
+    /// The caller must have at least shared access to the components registered in `self.registry`.
+    #[inline]
+    unsafe fn next_entry(&mut self) -> Option<TraitsEntry<'a, Trait>> {
+        loop {
+            let storage = self.next_storage()?;
+            let index = storage.index();
+            // SAFETY: The present impls were found in `self.registry`, so the index is in bounds.
+            let (component, meta) = unsafe {
+                (
+                    *self.registry.components.get_unchecked(index),
+                    self.registry.meta.get_unchecked(index),
+                )
+            };
+            let (ptr, ticks) = match storage {
+                // SAFETY: The row is within the table, so it is within the column.
+                ImplStorage::Table { column, .. } => unsafe {
+                    let ptr = column
+                        .get_data_ptr()
+                        .byte_add(self.table_row.index() * meta.size_bytes);
+                    let ticks = TickCells {
+                        added: column.get_added_ticks_unchecked(self.table_row),
+                        changed: column.get_changed_ticks_unchecked(self.table_row),
+                    };
+                    (ptr, ticks)
+                },
+                ImplStorage::SparseSet { components, .. } => {
+                    let Some(entry) = components.get_with_ticks(self.entity) else {
+                        continue;
+                    };
+                    entry
+                }
+            };
+            // SAFETY: The caller has at least shared access to the component, including its ticks.
+            let matches = unsafe {
+                self.filter
+                    .matches(ticks.added, ticks.changed, self.last_run, self.this_run)
+            };
+            if matches {
+                return Some((component, meta, (ptr, ticks)));
+            }
+        }
+    }
+}
 
 /// Read-access to all components implementing a trait for a given entity.
 pub struct ReadTraits<'a, Trait: ?Sized + TraitQuery> {
-    // Read-only access to the global trait registry.
+    // Read-only access to the impls in the global trait registry that are known to the query.
     // Since no one outside of the crate can name the registry type,
     // we can be confident that no write accesses will conflict with this.
-    registry: &'a TraitImplRegistry<Trait>,
+    registry: &'a ImplLists<Trait>,
     table: &'a Table,
     table_row: TableRow,
+    entity: Entity,
     /// This grants shared access to all sparse set components,
     /// but in practice we will only read the components specified in `self.registry`.
     /// The fetch impl registers read-access for all of these components,
     /// so there will be no runtime conflicts.
     sparse_sets: &'a SparseSets,
+    /// The registered impls that exist in the entity's table and archetype.
+    impls: PresentImpls<'a>,
+    filter: TickFilter,
+    last_run: Tick,
+    this_run: Tick,
 }
 
 #[doc(hidden)]
-pub type CombinedReadTraitsIter<'a, Trait> =
-    std::iter::Chain<ReadTableTraitsIter<'a, Trait>, ReadSparseTraitsIter<'a, Trait>>;
+pub struct CombinedReadTraitsIter<'a, Trait: ?Sized> {
+    // Grants shared access to the components registered in `entries.registry` for the entity.
+    entries: EntityImplsIter<'a, Trait>,
+}
+
+impl<'a, Trait: ?Sized + TraitQuery> Iterator for CombinedReadTraitsIter<'a, Trait> {
+    type Item = &'a Trait;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, meta, (ptr, _)) = self.next_entry()?;
+        // SAFETY: We have shared access to the component, which was registered with `meta`.
+        Some(unsafe { meta.dyn_ctor.cast(ptr) })
+    }
+}
 
+impl<'a, Trait: ?Sized + TraitQuery> CombinedReadTraitsIter<'a, Trait> {
+    /// Returns the next impl for the entity, in priority order.
+    #[inline]
+    fn next_entry(&mut self) -> Option<TraitsEntry<'a, Trait>> {
+        // SAFETY: We have shared access to every registered component of the entity.
+        unsafe { self.entries.next_entry() }
+    }
+}
+
+/// A component yielded for an entity, along with its ticks and the impl that it was registered with.
+/// The trait object is only created when needed, which keeps plain iteration cheap.
+type TraitsEntry<'a, Trait> = (
+    ComponentId,
+    &'a TraitImplMeta<Trait>,
+    (Ptr<'a>, TickCells<'a>),
+);
+
+/// Iterator over the components implementing a trait for a given entity,
+/// with change detection for each component.
 #[doc(hidden)]
-pub struct ReadTableTraitsIter<'a, Trait: ?Sized> {
-    // SAFETY: These two iterators must have equal length.
-    components: std::slice::Iter<'a, ComponentId>,
-    meta: std::slice::Iter<'a, TraitImplMeta<Trait>>,
-    table_row: TableRow,
-    // Grants shared access to the components corresponding to `components` in this table.
-    // Not all components are guaranteed to exist in the table.
-    table: &'a Table,
+pub struct ReadTraitsRefIter<'a, Trait: ?Sized> {
+    entries: CombinedReadTraitsIter<'a, Trait>,
 }
 
-impl<'a, Trait: ?Sized + TraitQuery> Iterator for ReadTableTraitsIter<'a, Trait> {
-    type Item = &'a Trait;
+impl<'a, Trait: ?Sized + TraitQuery> Iterator for ReadTraitsRefIter<'a, Trait> {
+    type Item = Ref<'a, Trait>;
+    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        // Iterate the remaining table components that are registered,
-        // until we find one that exists in the table.
-        let (column, meta) = unsafe { zip_exact(&mut self.components, &mut self.meta) }
-            .find_map(|(&component, meta)| self.table.get_column(component).zip(Some(meta)))?;
-        // SAFETY: We have shared access to the entire column.
-        let ptr = unsafe {
-            column
-                .get_data_ptr()
-                .byte_add(self.table_row.index() * meta.size_bytes)
-        };
+        let (_, meta, (ptr, ticks)) = self.entries.next_entry()?;
+        // SAFETY: We have shared access to the component, so by extension
+        // we have shared access to the corresponding `ComponentTicks`.
         let trait_object = unsafe { meta.dyn_ctor.cast(ptr) };
-        Some(trait_object)
+        let (added, changed) = unsafe { (ticks.added.deref(), ticks.changed.deref()) };
+        let entries = &self.entries.entries;
+        let (last_run, this_run) = (entries.last_run, entries.this_run);
+        Some(Ref::new(trait_object, added, changed, last_run, this_run))
     }
 }
 
+/// Iterator over the components implementing a trait for a given entity,
+/// along with the [`ComponentId`] of each component.
 #[doc(hidden)]
-pub struct ReadSparseTraitsIter<'a, Trait: ?Sized> {
-    // SAFETY: These two iterators must have equal length.
-    components: std::slice::Iter<'a, ComponentId>,
-    meta: std::slice::Iter<'a, TraitImplMeta<Trait>>,
-    entity: Entity,
-    // Grants shared access to the components corresponding to both `components` and `entity`.
-    sparse_sets: &'a SparseSets,
+pub struct ReadTraitsWithIdsIter<'a, Trait: ?Sized> {
+    entries: CombinedReadTraitsIter<'a, Trait>,
 }
 
-impl<'a, Trait: ?Sized + TraitQuery> Iterator for ReadSparseTraitsIter<'a, Trait> {
-    type Item = &'a Trait;
+impl<'a, Trait: ?Sized + TraitQuery> Iterator for ReadTraitsWithIdsIter<'a, Trait> {
+    type Item = (ComponentId, &'a Trait);
+    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        // Iterate the remaining sparse set components that are registered,
-        // until we find one that exists in the archetype.
-        let (ptr, meta) = unsafe { zip_exact(&mut self.components, &mut self.meta) }.find_map(
-            |(&component, meta)| {
-                self.sparse_sets
-                    .get(component)
-                    .and_then(|set| set.get(self.entity))
-                    .zip(Some(meta))
-            },
-        )?;
-        let trait_object = unsafe { meta.dyn_ctor.cast(ptr) };
-        Some(trait_object)
+        let (component, meta, (ptr, _)) = self.entries.next_entry()?;
+        // SAFETY: We have shared access to the component, which was registered with `meta`.
+        Some((component, unsafe { meta.dyn_ctor.cast(ptr) }))
+    }
+}
+
+/// Iterator over the components implementing a trait for a given entity,
+/// along with a description of the concrete type of each component.
+#[doc(hidden)]
+pub struct ReadTraitsWithInfoIter<'a, Trait: ?Sized> {
+    entries: CombinedReadTraitsIter<'a, Trait>,
+}
+
+impl<'a, Trait: ?Sized + TraitQuery> Iterator for ReadTraitsWithInfoIter<'a, Trait> {
+    type Item = (TraitImplInfo, &'a Trait);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (component, meta, (ptr, _)) = self.entries.next_entry()?;
+        // SAFETY: We have shared access to the component, which was registered with `meta`.
+        Some((meta.info(component), unsafe { meta.dyn_ctor.cast(ptr) }))
     }
 }
 
+/// Looks up the value and ticks of a single component for the entity at `table_row`,
+/// returning `None` if the entity doesn't have the component or it doesn't pass `filter`.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+fn get_with_ticks<'w>(
+    component: ComponentId,
+    storage_type: StorageType,
+    table: &'w Table,
+    table_row: TableRow,
+    entity: Entity,
+    sparse_sets: &'w SparseSets,
+    filter: TickFilter,
+    last_run: Tick,
+    this_run: Tick,
+) -> Option<(Ptr<'w>, TickCells<'w>)> {
+    let (ptr, ticks) = match storage_type {
+        StorageType::Table => table.get_column(component)?.get(table_row)?,
+        StorageType::SparseSet => sparse_sets.get(component)?.get_with_ticks(entity)?,
+    };
+    // SAFETY: The caller has at least shared access to the component, including its ticks.
+    let matches = unsafe { filter.matches(ticks.added, ticks.changed, last_run, this_run) };
+    matches.then_some((ptr, ticks))
+}
+
+impl<'w, Trait: ?Sized + TraitQuery> ReadTraits<'w, Trait> {
+    /// Returns the currently registered impls of `Trait` for `entity`,
+    /// or `None` if the entity doesn't exist or has none of them.
+    ///
+    /// # Safety
+    /// The caller must ensure that the entity's components may be read for the lifetime `'w`.
+    pub(crate) unsafe fn for_entity(world: UnsafeWorldCell<'w>, entity: Entity) -> Option<Self> {
+        let (registry, table, table_row, impls) = entity_impls(world, entity)?;
+        Some(Self {
+            registry,
+            table,
+            table_row,
+            entity,
+            sparse_sets: &world.storages().sparse_sets,
+            impls,
+            filter: TickFilter::None,
+            last_run: world.last_change_tick(),
+            this_run: world.change_tick(),
+        })
+    }
+}
+
+impl<'w, Trait: ?Sized + TraitQuery> WriteTraits<'w, Trait> {
+    /// Returns the currently registered impls of `Trait` for `entity`,
+    /// or `None` if the entity doesn't exist or has none of them.
+    ///
+    /// # Safety
+    /// The caller must ensure that the entity's components may be mutated for the lifetime `'w`,
+    /// and that nothing else accesses them during that time.
+    pub(crate) unsafe fn for_entity(world: UnsafeWorldCell<'w>, entity: Entity) -> Option<Self> {
+        let (registry, table, table_row, impls) = entity_impls(world, entity)?;
+        Some(Self {
+            registry,
+            table,
+            table_row,
+            entity,
+            impls,
+            filter: TickFilter::None,
+            last_run: world.last_change_tick(),
+            this_run: world.change_tick(),
+            sparse_sets: &world.storages().sparse_sets,
+        })
+    }
+}
+
+/// Finds the storage location of `entity`, and the currently registered impls of `Trait` that it has.
+///
+/// # Safety
+/// The caller must ensure that the entity's table may be accessed for the lifetime `'w`.
+unsafe fn entity_impls<'w, Trait: ?Sized + TraitQuery>(
+    world: UnsafeWorldCell<'w>,
+    entity: Entity,
+) -> Option<(&'w ImplLists<Trait>, &'w Table, TableRow, PresentImpls<'w>)> {
+    let registry = &world.get_resource::<TraitImplRegistry<Trait>>()?.lists;
+    let location = world.entities().get(entity)?;
+    let archetype = world.archetypes().get(location.archetype_id)?;
+    let table = world.storages().tables.get(location.table_id)?;
+    let sparse_sets = &world.storages().sparse_sets;
+    let impls = PresentImpls::in_archetype(registry, archetype, table, sparse_sets);
+    if impls.is_empty() {
+        return None;
+    }
+    Some((registry, table, location.table_row, impls))
+}
+
 impl<'w, Trait: ?Sized + TraitQuery> IntoIterator for ReadTraits<'w, Trait> {
     type Item = &'w Trait;
     type IntoIter = CombinedReadTraitsIter<'w, Trait>;
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        let table = ReadTableTraitsIter {
-            components: self.registry.table_components.iter(),
-            meta: self.registry.table_meta.iter(),
-            table: self.table,
-            table_row: self.table_row,
-        };
-        let sparse = ReadSparseTraitsIter {
-            components: self.registry.sparse_components.iter(),
-            meta: self.registry.sparse_meta.iter(),
-            entity: self.table.entities()[self.table_row.index()],
-            sparse_sets: self.sparse_sets,
-        };
-        table.chain(sparse)
+        self.entries()
     }
 }
 
@@ -113,19 +466,7 @@ impl<'w, Trait: ?Sized + TraitQuery> IntoIterator for &ReadTraits<'w, Trait> {
     type IntoIter = CombinedReadTraitsIter<'w, Trait>;
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        let table = ReadTableTraitsIter {
-            components: self.registry.table_components.iter(),
-            meta: self.registry.table_meta.iter(),
-            table: self.table,
-            table_row: self.table_row,
-        };
-        let sparse = ReadSparseTraitsIter {
-            components: self.registry.sparse_components.iter(),
-            meta: self.registry.sparse_meta.iter(),
-            entity: self.table.entities()[self.table_row.index()],
-            sparse_sets: self.sparse_sets,
-        };
-        table.chain(sparse)
+        self.entries()
     }
 }
 
@@ -134,25 +475,94 @@ impl<'w, Trait: ?Sized + TraitQuery> ReadTraits<'w, Trait> {
     pub fn iter(&self) -> CombinedReadTraitsIter<'w, Trait> {
         self.into_iter()
     }
+    /// Returns an iterator over the components implementing `Trait` for the current entity,
+    /// wrapping each one in a [`Ref`] to allow for change detection.
+    pub fn iter_ref(&self) -> ReadTraitsRefIter<'w, Trait> {
+        ReadTraitsRefIter {
+            entries: self.entries(),
+        }
+    }
+    /// Returns an iterator over the components implementing `Trait` for the current entity,
+    /// along with the [`ComponentId`] of each component.
+    pub fn iter_with_ids(&self) -> ReadTraitsWithIdsIter<'w, Trait> {
+        ReadTraitsWithIdsIter {
+            entries: self.entries(),
+        }
+    }
+    /// Returns an iterator over the components implementing `Trait` for the current entity,
+    /// along with the [`ComponentId`], [`TypeId`](std::any::TypeId) and type name of each component.
+    pub fn iter_with_info(&self) -> ReadTraitsWithInfoIter<'w, Trait> {
+        ReadTraitsWithInfoIter {
+            entries: self.entries(),
+        }
+    }
+    /// Returns the component of type `C` for the current entity, if it has one.
+    ///
+    /// Returns `None` if `C` has not been registered as an implementation of `Trait`.
+    /// When fetched through [`ChangedAll`] or [`AddedAll`], this also returns `None`
+    /// if `C` has not been changed or added since the last run, just like the iterators.
+    pub fn get<C: Component>(&self) -> Option<&'w C> {
+        let component = self.registry.component_of::<C>()?;
+        let (ptr, _) = get_with_ticks(
+            component,
+            C::Storage::STORAGE_TYPE,
+            self.table,
+            self.table_row,
+            self.entity,
+            self.sparse_sets,
+            self.filter,
+            self.last_run,
+            self.this_run,
+        )?;
+        // SAFETY: We have shared access to every component registered with the trait,
+        // and `component` was registered for the type `C`.
+        Some(unsafe { ptr.deref::<C>() })
+    }
+
+    #[inline]
+    fn entries(&self) -> CombinedReadTraitsIter<'w, Trait> {
+        CombinedReadTraitsIter {
+            entries: EntityImplsIter {
+                registry: self.registry,
+                impls: self.impls,
+                next: 0,
+                table: self.table,
+                table_row: self.table_row,
+                entity: self.entity,
+                sparse_sets: self.sparse_sets,
+                filter: self.filter,
+                last_run: self.last_run,
+                this_run: self.this_run,
+            },
+        }
+    }
 }
 
 #[doc(hidden)]
 pub struct ReadAllTraitsFetch<'w, Trait: ?Sized> {
-    registry: &'w TraitImplRegistry<Trait>,
+    registry: &'w ImplLists<Trait>,
     table: Option<&'w Table>,
     sparse_sets: &'w SparseSets,
+    impls: PresentImpls<'w>,
+    filter: TickFilter,
+    last_run: Tick,
+    this_run: Tick,
 }
 
 /// Write-access to all components implementing a trait for a given entity.
 pub struct WriteTraits<'a, Trait: ?Sized + TraitQuery> {
-    // Read-only access to the global trait registry.
+    // Read-only access to the impls in the global trait registry that are known to the query.
     // Since no one outside of the crate can name the registry type,
     // we can be confident that no write accesses will conflict with this.
-    registry: &'a TraitImplRegistry<Trait>,
+    registry: &'a ImplLists<Trait>,
 
     table: &'a Table,
     table_row: TableRow,
+    entity: Entity,
 
+    /// The registered impls that exist in the entity's table and archetype.
+    impls: PresentImpls<'a>,
+    filter: TickFilter,
     last_run: Tick,
     this_run: Tick,
 
@@ -164,106 +574,29 @@ pub struct WriteTraits<'a, Trait: ?Sized + TraitQuery> {
 }
 
 #[doc(hidden)]
-pub type CombinedWriteTraitsIter<'a, Trait> =
-    std::iter::Chain<WriteTableTraitsIter<'a, Trait>, WriteSparseTraitsIter<'a, Trait>>;
-
-#[doc(hidden)]
-pub struct WriteTableTraitsIter<'a, Trait: ?Sized> {
-    // SAFETY: These two iterators must have equal length.
-    components: std::slice::Iter<'a, ComponentId>,
-    meta: std::slice::Iter<'a, TraitImplMeta<Trait>>,
-    table: &'a Table,
-    /// SAFETY: Given the same trait type and same archetype,
-    /// no two instances of this struct may have the same `table_row`.
-    table_row: TableRow,
-    last_run: Tick,
-    this_run: Tick,
-}
-
-impl<'a, Trait: ?Sized + TraitQuery> Iterator for WriteTableTraitsIter<'a, Trait> {
-    type Item = Mut<'a, Trait>;
-    fn next(&mut self) -> Option<Self::Item> {
-        // Iterate the remaining table components that are registered,
-        // until we find one that exists in the table.
-        let (column, meta) = unsafe { zip_exact(&mut self.components, &mut self.meta) }
-            .find_map(|(&component, meta)| self.table.get_column(component).zip(Some(meta)))?;
-        let ptr = unsafe {
-            column
-                .get_data_ptr()
-                .byte_add(self.table_row.index() * meta.size_bytes)
-        };
-        // SAFETY: The instance of `WriteTraits` that created this iterator
-        // has exclusive access to all table components registered with the trait.
-        //
-        // Since `self.table_row` is guaranteed to be unique, we know that other instances
-        // of `WriteTableTraitsIter` will not conflict with this pointer.
-        let ptr = unsafe { ptr.assert_unique() };
-        let trait_object = unsafe { meta.dyn_ctor.cast_mut(ptr) };
-        // SAFETY: We have exclusive access to the component, so by extension
-        // we have exclusive access to the corresponding `ComponentTicks`.
-        let added = unsafe { column.get_added_ticks_unchecked(self.table_row).deref_mut() };
-        let changed = unsafe {
-            column
-                .get_changed_ticks_unchecked(self.table_row)
-                .deref_mut()
-        };
-        Some(Mut::new(
-            trait_object,
-            added,
-            changed,
-            self.last_run,
-            self.this_run,
-        ))
-    }
-}
-
-#[doc(hidden)]
-pub struct WriteSparseTraitsIter<'a, Trait: ?Sized> {
-    // SAFETY: These two iterators must have equal length.
-    components: std::slice::Iter<'a, ComponentId>,
-    meta: std::slice::Iter<'a, TraitImplMeta<Trait>>,
+pub struct CombinedWriteTraitsIter<'a, Trait: ?Sized> {
     /// SAFETY: Given the same trait type and same archetype,
-    /// no two instances of this struct may have the same `entity`.
-    entity: Entity,
-    sparse_sets: &'a SparseSets,
-    last_run: Tick,
-    this_run: Tick,
+    /// no two instances of this struct may have the same entity.
+    entries: EntityImplsIter<'a, Trait>,
 }
 
-impl<'a, Trait: ?Sized + TraitQuery> Iterator for WriteSparseTraitsIter<'a, Trait> {
+impl<'a, Trait: ?Sized + TraitQuery> Iterator for CombinedWriteTraitsIter<'a, Trait> {
     type Item = Mut<'a, Trait>;
+    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        // Iterate the remaining sparse set components we have registered,
-        // until we find one that exists in the archetype.
-        let ((ptr, component_ticks), meta) =
-            unsafe { zip_exact(&mut self.components, &mut self.meta) }.find_map(
-                |(&component, meta)| {
-                    self.sparse_sets
-                        .get(component)
-                        .and_then(|set| set.get_with_ticks(self.entity))
-                        .zip(Some(meta))
-                },
-            )?;
-
         // SAFETY: The instance of `WriteTraits` that created this iterator
-        // has exclusive access to all sparse set components registered with the trait.
-        //
-        // Since `self.entity` is guaranteed to be unique, we know that other instances
-        // of `WriteSparseTraitsIter` will not conflict with this pointer.
+        // has exclusive access to all components registered with the trait.
+        let (_, meta, (ptr, ticks)) = unsafe { self.entries.next_entry()? };
+        // Since the entity is guaranteed to be unique, we know that other instances
+        // of `CombinedWriteTraitsIter` will not conflict with this pointer.
         let ptr = unsafe { ptr.assert_unique() };
         let trait_object = unsafe { meta.dyn_ctor.cast_mut(ptr) };
         // SAFETY: We have exclusive access to the component, so by extension
         // we have exclusive access to the corresponding `ComponentTicks`.
-        let added = unsafe { component_ticks.added.deref_mut() };
-        let changed = unsafe { component_ticks.changed.deref_mut() };
-
-        Some(Mut::new(
-            trait_object,
-            added,
-            changed,
-            self.last_run,
-            self.this_run,
-        ))
+        let added = unsafe { ticks.added.deref_mut() };
+        let changed = unsafe { ticks.changed.deref_mut() };
+        let (last_run, this_run) = (self.entries.last_run, self.entries.this_run);
+        Some(Mut::new(trait_object, added, changed, last_run, this_run))
     }
 }
 
@@ -276,30 +609,97 @@ impl<'w, Trait: ?Sized + TraitQuery> WriteTraits<'w, Trait> {
     pub fn iter_mut(&mut self) -> CombinedWriteTraitsIter<'_, Trait> {
         self.into_iter()
     }
-}
+    /// Returns an iterator over the components implementing `Trait` for the current entity,
+    /// along with the [`ComponentId`] of each component.
+    pub fn iter_with_ids(&self) -> ReadTraitsWithIdsIter<'_, Trait> {
+        self.as_read().iter_with_ids()
+    }
+    /// Returns an iterator over the components implementing `Trait` for the current entity,
+    /// along with the [`ComponentId`], [`TypeId`](std::any::TypeId) and type name of each component.
+    pub fn iter_with_info(&self) -> ReadTraitsWithInfoIter<'_, Trait> {
+        self.as_read().iter_with_info()
+    }
+    /// Returns the component of type `C` for the current entity, if it has one.
+    ///
+    /// Returns `None` if `C` has not been registered as an implementation of `Trait`.
+    /// When fetched through [`ChangedAll`] or [`AddedAll`], this also returns `None`
+    /// if `C` has not been changed or added since the last run, just like the iterators.
+    pub fn get<C: Component>(&self) -> Option<&C> {
+        self.as_read().get::<C>()
+    }
+    /// Returns the component of type `C` for the current entity mutably, if it has one.
+    ///
+    /// Returns `None` if `C` has not been registered as an implementation of `Trait`.
+    /// When fetched through [`ChangedAll`] or [`AddedAll`], this also returns `None`
+    /// if `C` has not been changed or added since the last run, just like the iterators.
+    pub fn get_mut<C: Component>(&mut self) -> Option<Mut<'_, C>> {
+        let component = self.registry.component_of::<C>()?;
+        let (ptr, ticks) = get_with_ticks(
+            component,
+            C::Storage::STORAGE_TYPE,
+            self.table,
+            self.table_row,
+            self.entity,
+            self.sparse_sets,
+            self.filter,
+            self.last_run,
+            self.this_run,
+        )?;
+        // SAFETY: We have exclusive access to every component registered with the trait,
+        // and `component` was registered for the type `C`.
+        // The mutable borrow of `self` ensures that no other references to it are live.
+        let value = unsafe { ptr.assert_unique().deref_mut::<C>() };
+        let added = unsafe { ticks.added.deref_mut() };
+        let changed = unsafe { ticks.changed.deref_mut() };
+        Some(Mut::new(
+            value,
+            added,
+            changed,
+            self.last_run,
+            self.this_run,
+        ))
+    }
 
-impl<'w, Trait: ?Sized + TraitQuery> IntoIterator for WriteTraits<'w, Trait> {
-    type Item = Mut<'w, Trait>;
-    type IntoIter = CombinedWriteTraitsIter<'w, Trait>;
     #[inline]
-    fn into_iter(self) -> Self::IntoIter {
-        let table = WriteTableTraitsIter {
-            components: self.registry.table_components.iter(),
-            meta: self.registry.table_meta.iter(),
+    fn as_read(&self) -> ReadTraits<'_, Trait> {
+        ReadTraits {
+            registry: self.registry,
             table: self.table,
             table_row: self.table_row,
-            last_run: self.last_run,
-            this_run: self.this_run,
-        };
-        let sparse = WriteSparseTraitsIter {
-            components: self.registry.sparse_components.iter(),
-            meta: self.registry.sparse_meta.iter(),
-            entity: self.table.entities()[self.table_row.index()],
+            entity: self.entity,
             sparse_sets: self.sparse_sets,
+            impls: self.impls,
+            filter: self.filter,
             last_run: self.last_run,
             this_run: self.this_run,
-        };
-        table.chain(sparse)
+        }
+    }
+
+    #[inline]
+    fn entries_mut(&mut self) -> CombinedWriteTraitsIter<'w, Trait> {
+        CombinedWriteTraitsIter {
+            entries: EntityImplsIter {
+                registry: self.registry,
+                impls: self.impls,
+                next: 0,
+                table: self.table,
+                table_row: self.table_row,
+                entity: self.entity,
+                sparse_sets: self.sparse_sets,
+                filter: self.filter,
+                last_run: self.last_run,
+                this_run: self.this_run,
+            },
+        }
+    }
+}
+
+impl<'w, Trait: ?Sized + TraitQuery> IntoIterator for WriteTraits<'w, Trait> {
+    type Item = Mut<'w, Trait>;
+    type IntoIter = CombinedWriteTraitsIter<'w, Trait>;
+    #[inline]
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.entries_mut()
     }
 }
 
@@ -310,19 +710,7 @@ impl<'world, 'local, Trait: ?Sized + TraitQuery> IntoIterator
     type IntoIter = CombinedReadTraitsIter<'local, Trait>;
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        let table = ReadTableTraitsIter {
-            components: self.registry.table_components.iter(),
-            meta: self.registry.table_meta.iter(),
-            table: self.table,
-            table_row: self.table_row,
-        };
-        let sparse = ReadSparseTraitsIter {
-            components: self.registry.sparse_components.iter(),
-            meta: self.registry.sparse_meta.iter(),
-            entity: self.table.entities()[self.table_row.index()],
-            sparse_sets: self.sparse_sets,
-        };
-        table.chain(sparse)
+        self.as_read().into_iter()
     }
 }
 
@@ -333,31 +721,17 @@ impl<'world, 'local, Trait: ?Sized + TraitQuery> IntoIterator
     type IntoIter = CombinedWriteTraitsIter<'local, Trait>;
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        let table = WriteTableTraitsIter {
-            components: self.registry.table_components.iter(),
-            meta: self.registry.table_meta.iter(),
-            table: self.table,
-            table_row: self.table_row,
-            last_run: self.last_run,
-            this_run: self.this_run,
-        };
-        let sparse = WriteSparseTraitsIter {
-            components: self.registry.sparse_components.iter(),
-            meta: self.registry.sparse_meta.iter(),
-            entity: self.table.entities()[self.table_row.index()],
-            sparse_sets: self.sparse_sets,
-            last_run: self.last_run,
-            this_run: self.this_run,
-        };
-        table.chain(sparse)
+        self.entries_mut()
     }
 }
 
 #[doc(hidden)]
 pub struct WriteAllTraitsFetch<'w, Trait: ?Sized + TraitQuery> {
-    registry: &'w TraitImplRegistry<Trait>,
+    registry: &'w ImplLists<Trait>,
     table: Option<&'w Table>,
     sparse_sets: &'w SparseSets,
+    impls: PresentImpls<'w>,
+    filter: TickFilter,
     last_run: Tick,
     this_run: Tick,
 }
@@ -369,18 +743,34 @@ pub struct All<T: ?Sized>(T);
 
 /// `WorldQuery` adapter that fetches all implementations of a given trait for an entity, with
 /// the additional condition that they have also changed since the last tick.
+///
+/// This matches the same entities as [`All`]; entities with no changed impls
+/// simply yield an empty iterator.
 pub struct ChangedAll<T: ?Sized>(T);
 
 /// `WorldQuery` adapter that fetches all implementations of a given trait for an entity, with
 /// the additional condition that they have been added since the last tick.
+///
+/// This matches the same entities as [`All`]; entities with no newly-added impls
+/// simply yield an empty iterator.
 pub struct AddedAll<T: ?Sized>(T);
 
-unsafe impl<'a, Trait: ?Sized + TraitQuery> ReadOnlyWorldQuery for All<&'a Trait> {}
+/// `WorldQuery` adapter that fetches all implementations of a given trait for an entity,
+/// iterating over tables instead of archetypes.
+///
+/// It matches the same entities as [`All`], but it requires every component
+/// implementing the trait to be stored in a table.
+/// Whether iterating over tables is faster depends on how the entities are laid out,
+/// so measure before switching (see the `all`, `one` and `fragmented` benches).
+/// Initializing this query will panic if any of them use sparse set storage.
+pub struct DenseAll<T: ?Sized>(T);
+
+unsafe impl<Trait: ?Sized + TraitQuery> ReadOnlyWorldQuery for All<&Trait> {}
 
 /// SAFETY: We only access the components registered in the trait registry.
 /// This is known to match the set of components in the `DynQueryState`,
 /// which is used to match archetypes and register world access.
-unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a Trait> {
+unsafe impl<Trait: ?Sized + TraitQuery> WorldQuery for All<&Trait> {
     type Item<'w> = ReadTraits<'w, Trait>;
     type Fetch<'w> = ReadAllTraitsFetch<'w, Trait>;
     type ReadOnly = Self;
@@ -394,16 +784,22 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a Trait> {
     #[inline]
     unsafe fn init_fetch<'w>(
         world: UnsafeWorldCell<'w>,
-        _state: &Self::State,
-        _last_run: Tick,
-        _this_run: Tick,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
     ) -> ReadAllTraitsFetch<'w, Trait> {
         ReadAllTraitsFetch {
-            registry: world
-                .get_resource()
-                .unwrap_or_else(|| trait_registry_error()),
+            registry: state.registered_impls(
+                world
+                    .get_resource()
+                    .unwrap_or_else(|| trait_registry_error()),
+            ),
             table: None,
             sparse_sets: &world.storages().sparse_sets,
+            impls: PresentImpls::NONE,
+            filter: TickFilter::None,
+            last_run,
+            this_run,
         }
     }
 
@@ -413,6 +809,10 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a Trait> {
             registry: fetch.registry,
             table: fetch.table,
             sparse_sets: fetch.sparse_sets,
+            impls: fetch.impls,
+            filter: fetch.filter,
+            last_run: fetch.last_run,
+            this_run: fetch.this_run,
         }
     }
 
@@ -423,10 +823,12 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a Trait> {
     unsafe fn set_archetype<'w>(
         fetch: &mut ReadAllTraitsFetch<'w, Trait>,
         _state: &Self::State,
-        _archetype: &'w bevy::ecs::archetype::Archetype,
+        archetype: &'w bevy::ecs::archetype::Archetype,
         table: &'w bevy::ecs::storage::Table,
     ) {
         fetch.table = Some(table);
+        fetch.impls =
+            PresentImpls::in_archetype(fetch.registry, archetype, table, fetch.sparse_sets);
     }
 
     unsafe fn set_table<'w>(
@@ -435,12 +837,13 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a Trait> {
         table: &'w bevy::ecs::storage::Table,
     ) {
         fetch.table = Some(table);
+        fetch.impls = PresentImpls::in_table(fetch.registry, table);
     }
 
     #[inline]
     unsafe fn fetch<'w>(
         fetch: &mut Self::Fetch<'w>,
-        _entity: Entity,
+        entity: Entity,
         table_row: TableRow,
     ) -> Self::Item<'w> {
         let table = fetch.table.unwrap_or_else(|| debug_unreachable());
@@ -449,7 +852,12 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a Trait> {
             registry: fetch.registry,
             table,
             table_row,
+            entity,
             sparse_sets: fetch.sparse_sets,
+            impls: fetch.impls,
+            filter: fetch.filter,
+            last_run: fetch.last_run,
+            this_run: fetch.this_run,
         }
     }
 
@@ -464,8 +872,9 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a Trait> {
                 "&{} conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
                 std::any::type_name::<Trait>(),
             );
-            access.add_read(component);
+            access.access_mut().add_read(component);
         }
+        state.and_with_any(access);
     }
 
     #[inline]
@@ -511,16 +920,20 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a mut Trait> {
     #[inline]
     unsafe fn init_fetch<'w>(
         world: UnsafeWorldCell<'w>,
-        _state: &Self::State,
+        state: &Self::State,
         last_run: Tick,
         this_run: Tick,
     ) -> WriteAllTraitsFetch<'w, Trait> {
         WriteAllTraitsFetch {
-            registry: world
-                .get_resource()
-                .unwrap_or_else(|| trait_registry_error()),
+            registry: state.registered_impls(
+                world
+                    .get_resource()
+                    .unwrap_or_else(|| trait_registry_error()),
+            ),
             table: None,
             sparse_sets: &world.storages().sparse_sets,
+            impls: PresentImpls::NONE,
+            filter: TickFilter::None,
             last_run,
             this_run,
         }
@@ -532,6 +945,8 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a mut Trait> {
             registry: fetch.registry,
             table: fetch.table,
             sparse_sets: fetch.sparse_sets,
+            impls: fetch.impls,
+            filter: fetch.filter,
             last_run: fetch.last_run,
             this_run: fetch.this_run,
         }
@@ -544,10 +959,12 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a mut Trait> {
     unsafe fn set_archetype<'w>(
         fetch: &mut WriteAllTraitsFetch<'w, Trait>,
         _state: &Self::State,
-        _archetype: &'w bevy::ecs::archetype::Archetype,
+        archetype: &'w bevy::ecs::archetype::Archetype,
         table: &'w bevy::ecs::storage::Table,
     ) {
         fetch.table = Some(table);
+        fetch.impls =
+            PresentImpls::in_archetype(fetch.registry, archetype, table, fetch.sparse_sets);
     }
 
     #[inline]
@@ -557,12 +974,13 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a mut Trait> {
         table: &'w bevy::ecs::storage::Table,
     ) {
         fetch.table = Some(table);
+        fetch.impls = PresentImpls::in_table(fetch.registry, table);
     }
 
     #[inline]
     unsafe fn fetch<'w>(
         fetch: &mut Self::Fetch<'w>,
-        _entity: Entity,
+        entity: Entity,
         table_row: TableRow,
     ) -> Self::Item<'w> {
         let table = fetch.table.unwrap_or_else(|| debug_unreachable());
@@ -571,7 +989,10 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a mut Trait> {
             registry: fetch.registry,
             table,
             table_row,
+            entity,
             sparse_sets: fetch.sparse_sets,
+            impls: fetch.impls,
+            filter: fetch.filter,
             last_run: fetch.last_run,
             this_run: fetch.this_run,
         }
@@ -588,8 +1009,9 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a mut Trait> {
                 "&mut {} conflicts with a previous access in this query. Mutable component access must be unique.",
                 std::any::type_name::<Trait>(),
             );
-            access.add_write(component);
+            access.access_mut().add_write(component);
         }
+        state.and_with_any(access);
     }
 
     #[inline]
@@ -617,3 +1039,898 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for All<&'a mut Trait> {
         state.matches_component_set_any(set_contains_id)
     }
 }
+
+/// `WorldQuery` adapter that fetches all implementations of a given trait for an entity.
+///
+/// Unlike [`All`], this matches every entity: entities without any components
+/// implementing the trait will yield an empty iterator.
+pub struct MaybeAll<T: ?Sized>(T);
+
+unsafe impl<Trait: ?Sized + TraitQuery> ReadOnlyWorldQuery for MaybeAll<&Trait> {}
+
+/// SAFETY: This accesses the same components as `All<&Trait>`,
+/// which registers read access for all of them.
+unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for MaybeAll<&'a Trait> {
+    type Item<'w> = ReadTraits<'w, Trait>;
+    type Fetch<'w> = ReadAllTraitsFetch<'w, Trait>;
+    type ReadOnly = Self;
+    type State = TraitQueryState<Trait>;
+
+    #[inline]
+    fn shrink<'wlong: 'wshort, 'wshort>(item: QueryItem<'wlong, Self>) -> QueryItem<'wshort, Self> {
+        item
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        <All<&'a Trait> as WorldQuery>::init_fetch(world, state, last_run, this_run)
+    }
+
+    #[inline]
+    unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+        <All<&'a Trait> as WorldQuery>::clone_fetch(fetch)
+    }
+
+    const IS_DENSE: bool = <All<&'a Trait> as WorldQuery>::IS_DENSE;
+    const IS_ARCHETYPAL: bool = <All<&'a Trait> as WorldQuery>::IS_ARCHETYPAL;
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &Self::State,
+        archetype: &'w bevy::ecs::archetype::Archetype,
+        table: &'w bevy::ecs::storage::Table,
+    ) {
+        <All<&'a Trait> as WorldQuery>::set_archetype(fetch, state, archetype, table);
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &Self::State,
+        table: &'w bevy::ecs::storage::Table,
+    ) {
+        <All<&'a Trait> as WorldQuery>::set_table(fetch, state, table);
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Self::Item<'w> {
+        <All<&'a Trait> as WorldQuery>::fetch(fetch, entity, table_row)
+    }
+
+    #[inline]
+    fn update_component_access(
+        state: &Self::State,
+        access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+    ) {
+        // Since this matches every entity, we must not add the `With` filters from `All<>`.
+        let mut intermediate = access.clone();
+        <All<&'a Trait> as WorldQuery>::update_component_access(state, &mut intermediate);
+        access.extend_access(&intermediate);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &bevy::ecs::archetype::Archetype,
+        access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+    ) {
+        <All<&'a Trait> as WorldQuery>::update_archetype_component_access(state, archetype, access);
+    }
+
+    #[inline]
+    fn init_state(world: &mut World) -> Self::State {
+        <All<&'a Trait> as WorldQuery>::init_state(world)
+    }
+
+    #[inline]
+    fn matches_component_set(
+        _state: &Self::State,
+        _set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        true
+    }
+}
+
+/// SAFETY: This accesses the same components as `All<&mut Trait>`,
+/// which registers write access for all of them.
+unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for MaybeAll<&'a mut Trait> {
+    type Item<'w> = WriteTraits<'w, Trait>;
+    type Fetch<'w> = WriteAllTraitsFetch<'w, Trait>;
+    type ReadOnly = MaybeAll<&'a Trait>;
+    type State = TraitQueryState<Trait>;
+
+    #[inline]
+    fn shrink<'wlong: 'wshort, 'wshort>(item: QueryItem<'wlong, Self>) -> QueryItem<'wshort, Self> {
+        item
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        <All<&'a mut Trait> as WorldQuery>::init_fetch(world, state, last_run, this_run)
+    }
+
+    #[inline]
+    unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+        <All<&'a mut Trait> as WorldQuery>::clone_fetch(fetch)
+    }
+
+    const IS_DENSE: bool = <All<&'a mut Trait> as WorldQuery>::IS_DENSE;
+    const IS_ARCHETYPAL: bool = <All<&'a mut Trait> as WorldQuery>::IS_ARCHETYPAL;
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &Self::State,
+        archetype: &'w bevy::ecs::archetype::Archetype,
+        table: &'w bevy::ecs::storage::Table,
+    ) {
+        <All<&'a mut Trait> as WorldQuery>::set_archetype(fetch, state, archetype, table);
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &Self::State,
+        table: &'w bevy::ecs::storage::Table,
+    ) {
+        <All<&'a mut Trait> as WorldQuery>::set_table(fetch, state, table);
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Self::Item<'w> {
+        <All<&'a mut Trait> as WorldQuery>::fetch(fetch, entity, table_row)
+    }
+
+    #[inline]
+    fn update_component_access(
+        state: &Self::State,
+        access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+    ) {
+        // Since this matches every entity, we must not add the `With` filters from `All<>`.
+        let mut intermediate = access.clone();
+        <All<&'a mut Trait> as WorldQuery>::update_component_access(state, &mut intermediate);
+        access.extend_access(&intermediate);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &bevy::ecs::archetype::Archetype,
+        access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+    ) {
+        <All<&'a mut Trait> as WorldQuery>::update_archetype_component_access(
+            state, archetype, access,
+        );
+    }
+
+    #[inline]
+    fn init_state(world: &mut World) -> Self::State {
+        <All<&'a mut Trait> as WorldQuery>::init_state(world)
+    }
+
+    #[inline]
+    fn matches_component_set(
+        _state: &Self::State,
+        _set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        true
+    }
+}
+
+/// Implements `WorldQuery` for an adapter around `All<>` that only yields
+/// the trait impls which pass a `TickFilter`.
+macro_rules! impl_filtered_all {
+    ($name:ident, $filter:expr) => {
+        unsafe impl<Trait: ?Sized + TraitQuery> ReadOnlyWorldQuery for $name<&Trait> {}
+
+        /// SAFETY: This accesses the same components as `All<&Trait>`,
+        /// which registers read access for all of them.
+        unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for $name<&'a Trait> {
+            type Item<'w> = ReadTraits<'w, Trait>;
+            type Fetch<'w> = ReadAllTraitsFetch<'w, Trait>;
+            type ReadOnly = Self;
+            type State = TraitQueryState<Trait>;
+
+            #[inline]
+            fn shrink<'wlong: 'wshort, 'wshort>(
+                item: QueryItem<'wlong, Self>,
+            ) -> QueryItem<'wshort, Self> {
+                item
+            }
+
+            #[inline]
+            unsafe fn init_fetch<'w>(
+                world: UnsafeWorldCell<'w>,
+                state: &Self::State,
+                last_run: Tick,
+                this_run: Tick,
+            ) -> Self::Fetch<'w> {
+                let mut fetch =
+                    <All<&'a Trait> as WorldQuery>::init_fetch(world, state, last_run, this_run);
+                fetch.filter = $filter;
+                fetch
+            }
+
+            #[inline]
+            unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+                <All<&'a Trait> as WorldQuery>::clone_fetch(fetch)
+            }
+
+            const IS_DENSE: bool = <All<&'a Trait> as WorldQuery>::IS_DENSE;
+            const IS_ARCHETYPAL: bool = <All<&'a Trait> as WorldQuery>::IS_ARCHETYPAL;
+
+            #[inline]
+            unsafe fn set_archetype<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &Self::State,
+                archetype: &'w bevy::ecs::archetype::Archetype,
+                table: &'w bevy::ecs::storage::Table,
+            ) {
+                <All<&'a Trait> as WorldQuery>::set_archetype(fetch, state, archetype, table);
+            }
+
+            #[inline]
+            unsafe fn set_table<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &Self::State,
+                table: &'w bevy::ecs::storage::Table,
+            ) {
+                <All<&'a Trait> as WorldQuery>::set_table(fetch, state, table);
+            }
+
+            #[inline]
+            unsafe fn fetch<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                entity: Entity,
+                table_row: TableRow,
+            ) -> Self::Item<'w> {
+                <All<&'a Trait> as WorldQuery>::fetch(fetch, entity, table_row)
+            }
+
+            #[inline]
+            fn update_component_access(
+                state: &Self::State,
+                access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+            ) {
+                <All<&'a Trait> as WorldQuery>::update_component_access(state, access);
+            }
+
+            #[inline]
+            fn update_archetype_component_access(
+                state: &Self::State,
+                archetype: &bevy::ecs::archetype::Archetype,
+                access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+            ) {
+                <All<&'a Trait> as WorldQuery>::update_archetype_component_access(
+                    state, archetype, access,
+                );
+            }
+
+            #[inline]
+            fn init_state(world: &mut World) -> Self::State {
+                <All<&'a Trait> as WorldQuery>::init_state(world)
+            }
+
+            #[inline]
+            fn matches_component_set(
+                state: &Self::State,
+                set_contains_id: &impl Fn(ComponentId) -> bool,
+            ) -> bool {
+                <All<&'a Trait> as WorldQuery>::matches_component_set(state, set_contains_id)
+            }
+        }
+
+        /// SAFETY: This accesses the same components as `All<&mut Trait>`,
+        /// which registers write access for all of them.
+        unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for $name<&'a mut Trait> {
+            type Item<'w> = WriteTraits<'w, Trait>;
+            type Fetch<'w> = WriteAllTraitsFetch<'w, Trait>;
+            type ReadOnly = $name<&'a Trait>;
+            type State = TraitQueryState<Trait>;
+
+            #[inline]
+            fn shrink<'wlong: 'wshort, 'wshort>(
+                item: QueryItem<'wlong, Self>,
+            ) -> QueryItem<'wshort, Self> {
+                item
+            }
+
+            #[inline]
+            unsafe fn init_fetch<'w>(
+                world: UnsafeWorldCell<'w>,
+                state: &Self::State,
+                last_run: Tick,
+                this_run: Tick,
+            ) -> Self::Fetch<'w> {
+                let mut fetch = <All<&'a mut Trait> as WorldQuery>::init_fetch(
+                    world, state, last_run, this_run,
+                );
+                fetch.filter = $filter;
+                fetch
+            }
+
+            #[inline]
+            unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+                <All<&'a mut Trait> as WorldQuery>::clone_fetch(fetch)
+            }
+
+            const IS_DENSE: bool = <All<&'a mut Trait> as WorldQuery>::IS_DENSE;
+            const IS_ARCHETYPAL: bool = <All<&'a mut Trait> as WorldQuery>::IS_ARCHETYPAL;
+
+            #[inline]
+            unsafe fn set_archetype<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &Self::State,
+                archetype: &'w bevy::ecs::archetype::Archetype,
+                table: &'w bevy::ecs::storage::Table,
+            ) {
+                <All<&'a mut Trait> as WorldQuery>::set_archetype(fetch, state, archetype, table);
+            }
+
+            #[inline]
+            unsafe fn set_table<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &Self::State,
+                table: &'w bevy::ecs::storage::Table,
+            ) {
+                <All<&'a mut Trait> as WorldQuery>::set_table(fetch, state, table);
+            }
+
+            #[inline]
+            unsafe fn fetch<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                entity: Entity,
+                table_row: TableRow,
+            ) -> Self::Item<'w> {
+                <All<&'a mut Trait> as WorldQuery>::fetch(fetch, entity, table_row)
+            }
+
+            #[inline]
+            fn update_component_access(
+                state: &Self::State,
+                access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+            ) {
+                <All<&'a mut Trait> as WorldQuery>::update_component_access(state, access);
+            }
+
+            #[inline]
+            fn update_archetype_component_access(
+                state: &Self::State,
+                archetype: &bevy::ecs::archetype::Archetype,
+                access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+            ) {
+                <All<&'a mut Trait> as WorldQuery>::update_archetype_component_access(
+                    state, archetype, access,
+                );
+            }
+
+            #[inline]
+            fn init_state(world: &mut World) -> Self::State {
+                <All<&'a mut Trait> as WorldQuery>::init_state(world)
+            }
+
+            #[inline]
+            fn matches_component_set(
+                state: &Self::State,
+                set_contains_id: &impl Fn(ComponentId) -> bool,
+            ) -> bool {
+                <All<&'a mut Trait> as WorldQuery>::matches_component_set(state, set_contains_id)
+            }
+        }
+    };
+}
+
+impl_filtered_all!(ChangedAll, TickFilter::Changed);
+impl_filtered_all!(AddedAll, TickFilter::Added);
+
+/// Implements `WorldQuery` for a filter that checks whether any of an entity's
+/// trait impls pass a `TickFilter`.
+macro_rules! impl_any_tick_filter {
+    (
+        $(#[$meta:meta])*
+        $name:ident,
+        $filter:expr
+    ) => {
+        $(#[$meta])*
+        pub struct $name<Trait: ?Sized>(PhantomData<Trait>);
+
+        unsafe impl<Trait: ?Sized + TraitQuery> ReadOnlyWorldQuery for $name<Trait> {}
+
+        /// SAFETY: This accesses the same components as `All<&Trait>`,
+        /// which registers read access for all of them.
+        unsafe impl<Trait: ?Sized + TraitQuery> WorldQuery for $name<Trait> {
+            type Item<'w> = bool;
+            type Fetch<'w> = ReadAllTraitsFetch<'w, Trait>;
+            type ReadOnly = Self;
+            type State = TraitQueryState<Trait>;
+
+            #[inline]
+            fn shrink<'wlong: 'wshort, 'wshort>(
+                item: QueryItem<'wlong, Self>,
+            ) -> QueryItem<'wshort, Self> {
+                item
+            }
+
+            #[inline]
+            unsafe fn init_fetch<'w>(
+                world: UnsafeWorldCell<'w>,
+                state: &Self::State,
+                last_run: Tick,
+                this_run: Tick,
+            ) -> Self::Fetch<'w> {
+                let mut fetch =
+                    <All<&Trait> as WorldQuery>::init_fetch(world, state, last_run, this_run);
+                fetch.filter = $filter;
+                fetch
+            }
+
+            #[inline]
+            unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+                <All<&Trait> as WorldQuery>::clone_fetch(fetch)
+            }
+
+            const IS_DENSE: bool = <All<&Trait> as WorldQuery>::IS_DENSE;
+            const IS_ARCHETYPAL: bool = false;
+
+            #[inline]
+            unsafe fn set_archetype<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &Self::State,
+                archetype: &'w bevy::ecs::archetype::Archetype,
+                table: &'w bevy::ecs::storage::Table,
+            ) {
+                <All<&Trait> as WorldQuery>::set_archetype(fetch, state, archetype, table);
+            }
+
+            #[inline]
+            unsafe fn set_table<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &Self::State,
+                table: &'w bevy::ecs::storage::Table,
+            ) {
+                <All<&Trait> as WorldQuery>::set_table(fetch, state, table);
+            }
+
+            #[inline]
+            unsafe fn fetch<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                entity: Entity,
+                table_row: TableRow,
+            ) -> Self::Item<'w> {
+                // The fetched item will only yield trait impls that pass the filter.
+                <All<&Trait> as WorldQuery>::fetch(fetch, entity, table_row)
+                    .iter()
+                    .next()
+                    .is_some()
+            }
+
+            #[inline]
+            unsafe fn filter_fetch(
+                fetch: &mut Self::Fetch<'_>,
+                entity: Entity,
+                table_row: TableRow,
+            ) -> bool {
+                Self::fetch(fetch, entity, table_row)
+            }
+
+            #[inline]
+            fn update_component_access(
+                state: &Self::State,
+                access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+            ) {
+                <All<&Trait> as WorldQuery>::update_component_access(state, access);
+            }
+
+            #[inline]
+            fn update_archetype_component_access(
+                state: &Self::State,
+                archetype: &bevy::ecs::archetype::Archetype,
+                access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+            ) {
+                <All<&Trait> as WorldQuery>::update_archetype_component_access(
+                    state, archetype, access,
+                );
+            }
+
+            #[inline]
+            fn init_state(world: &mut World) -> Self::State {
+                <All<&Trait> as WorldQuery>::init_state(world)
+            }
+
+            #[inline]
+            fn matches_component_set(
+                state: &Self::State,
+                set_contains_id: &impl Fn(ComponentId) -> bool,
+            ) -> bool {
+                <All<&Trait> as WorldQuery>::matches_component_set(state, set_contains_id)
+            }
+        }
+    };
+}
+
+impl_any_tick_filter!(
+    /// [`WorldQuery`] filter for entities with at least one component implementing a trait
+    /// whose value has changed since the last time the system ran.
+    AnyChanged,
+    TickFilter::Changed
+);
+
+impl_any_tick_filter!(
+    /// [`WorldQuery`] filter for entities with at least one component implementing a trait
+    /// which was added since the last time the system ran.
+    AnyAdded,
+    TickFilter::Added
+);
+
+/// [`WorldQuery`] filter for entities with at least one component implementing a trait.
+///
+/// Unlike `&dyn Trait`, this does not register access to any components,
+/// so it will never conflict with other queries.
+pub struct WithTrait<Trait: ?Sized>(PhantomData<Trait>);
+
+unsafe impl<Trait: ?Sized + TraitQuery> ReadOnlyWorldQuery for WithTrait<Trait> {}
+
+/// SAFETY: This does not access any component data.
+unsafe impl<Trait: ?Sized + TraitQuery> WorldQuery for WithTrait<Trait> {
+    type Item<'w> = ();
+    type Fetch<'w> = ();
+    type ReadOnly = Self;
+    type State = TraitQueryState<Trait>;
+
+    #[inline]
+    fn shrink<'wlong: 'wshort, 'wshort>(_: QueryItem<'wlong, Self>) -> QueryItem<'wshort, Self> {}
+
+    #[inline]
+    unsafe fn init_fetch(
+        _world: UnsafeWorldCell<'_>,
+        _state: &Self::State,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn clone_fetch<'w>(_fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {}
+
+    // Trait impls may be stored in sparse sets, so we can't use dense iteration.
+    const IS_DENSE: bool = false;
+    const IS_ARCHETYPAL: bool = true;
+
+    #[inline]
+    unsafe fn set_archetype(
+        _fetch: &mut (),
+        _state: &Self::State,
+        _archetype: &bevy::ecs::archetype::Archetype,
+        _table: &bevy::ecs::storage::Table,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn set_table(_fetch: &mut (), _state: &Self::State, _table: &bevy::ecs::storage::Table) {
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(
+        _fetch: &mut Self::Fetch<'w>,
+        _entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+    }
+
+    #[inline]
+    fn update_component_access(
+        state: &Self::State,
+        access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+    ) {
+        state.and_with_any(access);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        _state: &Self::State,
+        _archetype: &bevy::ecs::archetype::Archetype,
+        _access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+    ) {
+    }
+
+    #[inline]
+    fn init_state(world: &mut World) -> Self::State {
+        TraitQueryState::init(world)
+    }
+
+    #[inline]
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        state.matches_component_set_any(set_contains_id)
+    }
+}
+
+/// [`WorldQuery`] filter for entities without any components implementing a trait.
+///
+/// This does not register access to any components, so it will never conflict with other queries.
+pub struct WithoutTrait<Trait: ?Sized>(PhantomData<Trait>);
+
+unsafe impl<Trait: ?Sized + TraitQuery> ReadOnlyWorldQuery for WithoutTrait<Trait> {}
+
+/// SAFETY: This does not access any component data.
+unsafe impl<Trait: ?Sized + TraitQuery> WorldQuery for WithoutTrait<Trait> {
+    type Item<'w> = ();
+    type Fetch<'w> = ();
+    type ReadOnly = Self;
+    type State = TraitQueryState<Trait>;
+
+    #[inline]
+    fn shrink<'wlong: 'wshort, 'wshort>(_: QueryItem<'wlong, Self>) -> QueryItem<'wshort, Self> {}
+
+    #[inline]
+    unsafe fn init_fetch(
+        _world: UnsafeWorldCell<'_>,
+        _state: &Self::State,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn clone_fetch<'w>(_fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {}
+
+    const IS_DENSE: bool = false;
+    const IS_ARCHETYPAL: bool = true;
+
+    #[inline]
+    unsafe fn set_archetype(
+        _fetch: &mut (),
+        _state: &Self::State,
+        _archetype: &bevy::ecs::archetype::Archetype,
+        _table: &bevy::ecs::storage::Table,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn set_table(_fetch: &mut (), _state: &Self::State, _table: &bevy::ecs::storage::Table) {
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(
+        _fetch: &mut Self::Fetch<'w>,
+        _entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+    }
+
+    #[inline]
+    fn update_component_access(
+        state: &Self::State,
+        access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+    ) {
+        for &component in &*state.components {
+            access.and_without(component);
+        }
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        _state: &Self::State,
+        _archetype: &bevy::ecs::archetype::Archetype,
+        _access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+    ) {
+    }
+
+    #[inline]
+    fn init_state(world: &mut World) -> Self::State {
+        TraitQueryState::init(world)
+    }
+
+    #[inline]
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        !state.matches_component_set_any(set_contains_id)
+    }
+}
+
+/// [`WorldQuery`] that returns whether an entity has any components implementing a trait.
+///
+/// This matches every entity, and does not register access to any components,
+/// so it will never conflict with other queries.
+pub struct HasTrait<Trait: ?Sized>(PhantomData<Trait>);
+
+unsafe impl<Trait: ?Sized + TraitQuery> ReadOnlyWorldQuery for HasTrait<Trait> {}
+
+/// SAFETY: This does not access any component data.
+unsafe impl<Trait: ?Sized + TraitQuery> WorldQuery for HasTrait<Trait> {
+    type Item<'w> = bool;
+    type Fetch<'w> = bool;
+    type ReadOnly = Self;
+    type State = TraitQueryState<Trait>;
+
+    #[inline]
+    fn shrink<'wlong: 'wshort, 'wshort>(item: QueryItem<'wlong, Self>) -> QueryItem<'wshort, Self> {
+        item
+    }
+
+    #[inline]
+    unsafe fn init_fetch(
+        _world: UnsafeWorldCell<'_>,
+        _state: &Self::State,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) -> bool {
+        false
+    }
+
+    #[inline]
+    unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+        *fetch
+    }
+
+    const IS_DENSE: bool = false;
+    const IS_ARCHETYPAL: bool = true;
+
+    #[inline]
+    unsafe fn set_archetype(
+        fetch: &mut bool,
+        state: &Self::State,
+        archetype: &bevy::ecs::archetype::Archetype,
+        _table: &bevy::ecs::storage::Table,
+    ) {
+        *fetch = state.matches_component_set_any(&|id| archetype.contains(id));
+    }
+
+    #[inline]
+    unsafe fn set_table(fetch: &mut bool, state: &Self::State, table: &bevy::ecs::storage::Table) {
+        *fetch = state.matches_component_set_any(&|id| table.has_column(id));
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        _entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+        *fetch
+    }
+
+    #[inline]
+    fn update_component_access(
+        _state: &Self::State,
+        _access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+    ) {
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        _state: &Self::State,
+        _archetype: &bevy::ecs::archetype::Archetype,
+        _access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+    ) {
+    }
+
+    #[inline]
+    fn init_state(world: &mut World) -> Self::State {
+        TraitQueryState::init(world)
+    }
+
+    #[inline]
+    fn matches_component_set(
+        _state: &Self::State,
+        _set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        true
+    }
+}
+
+/// [`WorldQuery`] that returns the number of components implementing a trait for an entity.
+///
+/// This matches every entity, and does not register access to any components,
+/// so it will never conflict with other queries.
+pub struct TraitCount<Trait: ?Sized>(PhantomData<Trait>);
+
+unsafe impl<Trait: ?Sized + TraitQuery> ReadOnlyWorldQuery for TraitCount<Trait> {}
+
+/// SAFETY: This does not access any component data.
+unsafe impl<Trait: ?Sized + TraitQuery> WorldQuery for TraitCount<Trait> {
+    type Item<'w> = usize;
+    type Fetch<'w> = usize;
+    type ReadOnly = Self;
+    type State = TraitQueryState<Trait>;
+
+    #[inline]
+    fn shrink<'wlong: 'wshort, 'wshort>(item: QueryItem<'wlong, Self>) -> QueryItem<'wshort, Self> {
+        item
+    }
+
+    #[inline]
+    unsafe fn init_fetch(
+        _world: UnsafeWorldCell<'_>,
+        _state: &Self::State,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) -> usize {
+        0
+    }
+
+    #[inline]
+    unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+        *fetch
+    }
+
+    const IS_DENSE: bool = false;
+    const IS_ARCHETYPAL: bool = true;
+
+    #[inline]
+    unsafe fn set_archetype(
+        fetch: &mut usize,
+        state: &Self::State,
+        archetype: &bevy::ecs::archetype::Archetype,
+        _table: &bevy::ecs::storage::Table,
+    ) {
+        *fetch = state.count_component_set(&|id| archetype.contains(id));
+    }
+
+    #[inline]
+    unsafe fn set_table(fetch: &mut usize, state: &Self::State, table: &bevy::ecs::storage::Table) {
+        *fetch = state.count_component_set(&|id| table.has_column(id));
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        _entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+        *fetch
+    }
+
+    #[inline]
+    fn update_component_access(
+        _state: &Self::State,
+        _access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+    ) {
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        _state: &Self::State,
+        _archetype: &bevy::ecs::archetype::Archetype,
+        _access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+    ) {
+    }
+
+    #[inline]
+    fn init_state(world: &mut World) -> Self::State {
+        TraitQueryState::init(world)
+    }
+
+    #[inline]
+    fn matches_component_set(
+        _state: &Self::State,
+        _set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        true
+    }
+}
+
+impl_dense_query!(DenseAll, All);
@@ -0,0 +1,82 @@
+//! [`Commands`](bevy::ecs::system::Commands) for working with trait impls.
+
+use crate::{TraitEntityMutExt, TraitQuery, TraitWorldExt};
+use bevy::ecs::change_detection::Mut;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::{Commands, EntityCommands};
+use bevy::ecs::world::World;
+
+/// Extension methods for queueing commands that affect the trait impls of an entity.
+pub trait TraitEntityCommandsExt {
+    /// Removes every component of the entity that implements `Trait`.
+    ///
+    /// See [`TraitEntityMutExt::remove_traits`].
+    fn remove_traits<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self;
+
+    /// Calls `f` with mutable access to each component of the entity that implements `Trait`,
+    /// in the same order that they would be visited by a `&mut dyn Trait` query.
+    ///
+    /// Change detection works the same as it does for queries.
+    fn modify_traits<Trait: ?Sized + TraitQuery>(
+        &mut self,
+        f: impl FnMut(Mut<Trait>) + Send + 'static,
+    ) -> &mut Self;
+
+    /// Calls `f` with mutable access to the component of the entity that implements `Trait`.
+    /// Does nothing if the entity does not have exactly one such component,
+    /// the same as a [`One`](crate::One) query.
+    fn modify_one_trait<Trait: ?Sized + TraitQuery>(
+        &mut self,
+        f: impl FnOnce(Mut<Trait>) + Send + 'static,
+    ) -> &mut Self;
+}
+
+impl TraitEntityCommandsExt for EntityCommands<'_, '_, '_> {
+    fn remove_traits<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self {
+        self.add(|entity: Entity, world: &mut World| {
+            if let Some(mut entity) = world.get_entity_mut(entity) {
+                entity.remove_traits::<Trait>();
+            }
+        })
+    }
+
+    fn modify_traits<Trait: ?Sized + TraitQuery>(
+        &mut self,
+        f: impl FnMut(Mut<Trait>) + Send + 'static,
+    ) -> &mut Self {
+        self.add(|entity: Entity, world: &mut World| {
+            if let Some(traits) = world.get_traits_mut::<Trait>(entity) {
+                traits.into_iter().for_each(f);
+            }
+        })
+    }
+
+    fn modify_one_trait<Trait: ?Sized + TraitQuery>(
+        &mut self,
+        f: impl FnOnce(Mut<Trait>) + Send + 'static,
+    ) -> &mut Self {
+        self.add(|entity: Entity, world: &mut World| {
+            let Some(traits) = world.get_traits_mut::<Trait>(entity) else {
+                return;
+            };
+            let mut traits = traits.into_iter();
+            if let (Some(one), None) = (traits.next(), traits.next()) {
+                f(one);
+            }
+        })
+    }
+}
+
+/// Extension methods for queueing commands that affect the trait impls of entities.
+pub trait TraitCommandsExt {
+    /// Clones every component of `source` that implements `Trait` and inserts it into `target`.
+    ///
+    /// See [`TraitWorldExt::clone_traits`].
+    fn clone_traits<Trait: ?Sized + TraitQuery>(&mut self, source: Entity, target: Entity);
+}
+
+impl TraitCommandsExt for Commands<'_, '_> {
+    fn clone_traits<Trait: ?Sized + TraitQuery>(&mut self, source: Entity, target: Entity) {
+        self.add(move |world: &mut World| world.clone_traits::<Trait>(source, target));
+    }
+}
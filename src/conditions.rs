@@ -0,0 +1,38 @@
+//! Run conditions based on trait queries.
+
+use crate::{AnyAdded, AnyChanged, TraitQuery, WithTrait};
+use bevy::ecs::system::Query;
+
+/// Generates a run condition that returns `true` if any entity has a component implementing `Trait`.
+///
+/// ```ignore
+/// # use bevy::prelude::*;
+/// # use bevy_trait_query::*;
+/// #
+/// # #[bevy_trait_query::queryable]
+/// # pub trait Tooltip {
+/// #     fn tooltip(&self) -> &str;
+/// # }
+/// #
+/// # fn layout_tooltips() {}
+/// let mut app = App::new();
+/// app.add_systems(Update, layout_tooltips.run_if(any_with_trait::<dyn Tooltip>()));
+/// ```
+pub fn any_with_trait<Trait: ?Sized + TraitQuery>(
+) -> impl FnMut(Query<(), WithTrait<Trait>>) -> bool + Clone {
+    move |query: Query<(), WithTrait<Trait>>| !query.is_empty()
+}
+
+/// Generates a run condition that returns `true` if any component implementing `Trait`
+/// has changed since the last time the condition was checked.
+pub fn any_trait_changed<Trait: ?Sized + TraitQuery>(
+) -> impl FnMut(Query<(), AnyChanged<Trait>>) -> bool + Clone {
+    move |query: Query<(), AnyChanged<Trait>>| !query.is_empty()
+}
+
+/// Generates a run condition that returns `true` if any component implementing `Trait`
+/// has been added since the last time the condition was checked.
+pub fn any_trait_added<Trait: ?Sized + TraitQuery>(
+) -> impl FnMut(Query<(), AnyAdded<Trait>>) -> bool + Clone {
+    move |query: Query<(), AnyAdded<Trait>>| !query.is_empty()
+}
@@ -0,0 +1,154 @@
+//! Access to the trait impls of individual entities, outside of queries.
+
+use crate::{ReadTraits, TraitImplRegistry, TraitQuery, WriteTraits};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::world::{EntityMut, EntityRef, World};
+use bevy::log::warn;
+
+/// Extension methods for accessing the trait impls of an [`EntityRef`].
+pub trait TraitEntityRefExt<'w> {
+    /// Returns every component of this entity that implements `Trait`,
+    /// or `None` if it doesn't have any.
+    fn get_traits<Trait: ?Sized + TraitQuery>(&self) -> Option<ReadTraits<'w, Trait>>;
+
+    /// Returns the component of this entity that implements `Trait`,
+    /// or `None` if it doesn't have exactly one.
+    fn get_one_trait<Trait: ?Sized + TraitQuery>(&self) -> Option<&'w Trait>;
+}
+
+impl<'w> TraitEntityRefExt<'w> for EntityRef<'w> {
+    fn get_traits<Trait: ?Sized + TraitQuery>(&self) -> Option<ReadTraits<'w, Trait>> {
+        // SAFETY: `EntityRef` grants read access to all of the entity's components.
+        unsafe { ReadTraits::for_entity(self.world().as_unsafe_world_cell_readonly(), self.id()) }
+    }
+
+    fn get_one_trait<Trait: ?Sized + TraitQuery>(&self) -> Option<&'w Trait> {
+        exactly_one(self.get_traits::<Trait>()?)
+    }
+}
+
+/// Extension methods for accessing the trait impls of an [`EntityMut`].
+pub trait TraitEntityMutExt {
+    /// Returns every component of this entity that implements `Trait`,
+    /// or `None` if it doesn't have any.
+    fn get_traits<Trait: ?Sized + TraitQuery>(&self) -> Option<ReadTraits<'_, Trait>>;
+
+    /// Returns mutable access to every component of this entity that implements `Trait`,
+    /// or `None` if it doesn't have any.
+    fn get_traits_mut<Trait: ?Sized + TraitQuery>(&mut self) -> Option<WriteTraits<'_, Trait>>;
+
+    /// Returns the component of this entity that implements `Trait`,
+    /// or `None` if it doesn't have exactly one.
+    fn get_one_trait<Trait: ?Sized + TraitQuery>(&self) -> Option<&Trait>;
+
+    /// Removes every component of this entity that implements `Trait`.
+    fn remove_traits<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self;
+}
+
+impl TraitEntityMutExt for EntityMut<'_> {
+    fn get_traits<Trait: ?Sized + TraitQuery>(&self) -> Option<ReadTraits<'_, Trait>> {
+        // SAFETY: `EntityMut` grants read access to all of the entity's components.
+        unsafe { ReadTraits::for_entity(self.world().as_unsafe_world_cell_readonly(), self.id()) }
+    }
+
+    fn get_traits_mut<Trait: ?Sized + TraitQuery>(&mut self) -> Option<WriteTraits<'_, Trait>> {
+        let entity = self.id();
+        // SAFETY: We don't move the entity, and `EntityMut` grants exclusive access to all of its components.
+        // The returned value borrows `self` mutably, so nothing else can access them in the meantime.
+        unsafe { WriteTraits::for_entity(self.world_mut().as_unsafe_world_cell(), entity) }
+    }
+
+    fn get_one_trait<Trait: ?Sized + TraitQuery>(&self) -> Option<&Trait> {
+        exactly_one(self.get_traits::<Trait>()?)
+    }
+
+    fn remove_traits<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self {
+        let Some(registry) = self.world().get_resource::<TraitImplRegistry<Trait>>() else {
+            return self;
+        };
+        let removals: Vec<_> = std::iter::zip(&registry.lists.components, &registry.lists.meta)
+            .filter(|(&component, _)| self.contains_id(component))
+            .map(|(_, &meta)| meta)
+            .collect();
+        for meta in removals {
+            meta.remove(self);
+        }
+        self
+    }
+}
+
+/// Extension methods for accessing the trait impls of entities in a [`World`].
+pub trait TraitWorldExt {
+    /// Returns every component of `entity` that implements `Trait`,
+    /// or `None` if the entity doesn't exist or doesn't have any.
+    fn get_traits<Trait: ?Sized + TraitQuery>(
+        &self,
+        entity: Entity,
+    ) -> Option<ReadTraits<'_, Trait>>;
+
+    /// Returns mutable access to every component of `entity` that implements `Trait`,
+    /// or `None` if the entity doesn't exist or doesn't have any.
+    fn get_traits_mut<Trait: ?Sized + TraitQuery>(
+        &mut self,
+        entity: Entity,
+    ) -> Option<WriteTraits<'_, Trait>>;
+
+    /// Clones every component of `source` that implements `Trait` and inserts it into `target`,
+    /// replacing any components of the same types that `target` already has.
+    ///
+    /// Only components registered with
+    /// [`register_cloneable_component_as`](crate::RegisterExt::register_cloneable_component_as)
+    /// can be cloned; a warning is logged for any other components implementing `Trait`.
+    ///
+    /// # Panics
+    /// If `target` does not exist.
+    fn clone_traits<Trait: ?Sized + TraitQuery>(&mut self, source: Entity, target: Entity);
+}
+
+impl TraitWorldExt for World {
+    fn get_traits<Trait: ?Sized + TraitQuery>(
+        &self,
+        entity: Entity,
+    ) -> Option<ReadTraits<'_, Trait>> {
+        self.get_entity(entity)?.get_traits()
+    }
+
+    fn get_traits_mut<Trait: ?Sized + TraitQuery>(
+        &mut self,
+        entity: Entity,
+    ) -> Option<WriteTraits<'_, Trait>> {
+        // SAFETY: We have exclusive access to the world.
+        unsafe { WriteTraits::for_entity(self.as_unsafe_world_cell(), entity) }
+    }
+
+    fn clone_traits<Trait: ?Sized + TraitQuery>(&mut self, source: Entity, target: Entity) {
+        let Some(registry) = self.get_resource::<TraitImplRegistry<Trait>>() else {
+            return;
+        };
+        let Some(source_ref) = self.get_entity(source) else {
+            return;
+        };
+        let clones: Vec<_> = std::iter::zip(&registry.lists.components, &registry.lists.meta)
+            .filter(|(&component, _)| source_ref.contains_id(component))
+            .filter_map(|(_, meta)| {
+                if meta.clone_into.is_none() {
+                    warn!(
+                        "`{}` can't be cloned as `{}`, since it was not registered as cloneable",
+                        meta.type_name,
+                        std::any::type_name::<Trait>(),
+                    );
+                }
+                meta.clone_into
+            })
+            .collect();
+        for clone_into in clones {
+            clone_into(self, source, target);
+        }
+    }
+}
+
+fn exactly_one<'a, Trait: ?Sized + TraitQuery>(traits: ReadTraits<'a, Trait>) -> Option<&'a Trait> {
+    let mut iter = traits.into_iter();
+    let first = iter.next()?;
+    iter.next().is_none().then_some(first)
+}
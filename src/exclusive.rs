@@ -0,0 +1,150 @@
+use crate::{TraitImplMeta, TraitImplRegistry, TraitQuery};
+use bevy::ecs::archetype::ArchetypeId;
+use bevy::ecs::component::{ComponentId, Tick};
+use bevy::ecs::system::Local;
+use bevy::ecs::world::World;
+use bevy::log::warn;
+
+/// Determines what happens to entities with more than one component implementing an exclusive trait.
+///
+/// See [`RegisterExt::validate_exclusive_trait`](crate::RegisterExt::validate_exclusive_trait).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExclusivePolicy {
+    /// Log a warning the first time an archetype with multiple impls of the trait is populated.
+    Warn,
+    /// Panic as soon as an entity with multiple impls of the trait is found.
+    Panic,
+    /// Keep the component that was added most recently, and remove the others.
+    /// If several of them were added at the same time, the one with the highest priority is kept.
+    RemoveOlder,
+}
+
+/// Detects entities with more than one component implementing `Trait`,
+/// and handles them according to the [`ExclusivePolicy`] it was registered with.
+///
+/// Does nothing unless `Trait` has been registered with
+/// [`RegisterExt::validate_exclusive_trait`](crate::RegisterExt::validate_exclusive_trait).
+///
+/// Conflicts are only found when this system runs, not when the components are inserted.
+/// Until then, queries can see entities with more than one impl of `Trait`,
+/// and [`One`](crate::One) queries skip them.
+/// Running it right after the systems that insert these components keeps that window short.
+pub fn validate_exclusive_trait<Trait: ?Sized + TraitQuery>(
+    world: &mut World,
+    mut conflicts: Local<ExclusiveConflicts>,
+) {
+    let Some(registry) = world.get_resource::<TraitImplRegistry<Trait>>() else {
+        return;
+    };
+    let Some(policy) = registry.exclusive else {
+        return;
+    };
+
+    // If impls have been registered or unregistered, every archetype needs to be checked again.
+    if conflicts.generation != registry.generation {
+        *conflicts = ExclusiveConflicts {
+            generation: registry.generation,
+            ..Default::default()
+        };
+    }
+
+    // Archetypes are never removed, so we only need to check the ones created since the last run.
+    let archetypes = world.archetypes();
+    for archetype in archetypes.iter().skip(conflicts.archetypes_seen) {
+        let mut impls = (registry.lists.components.iter()).filter(|&&c| archetype.contains(c));
+        if impls.nth(1).is_some() {
+            conflicts.archetypes.push(archetype.id());
+        }
+    }
+    conflicts.archetypes_seen = archetypes.len();
+
+    let impls: Vec<(ComponentId, TraitImplMeta<Trait>)> = std::iter::zip(
+        registry.lists.components.iter().copied(),
+        registry.lists.meta.iter().copied(),
+    )
+    .collect();
+    let mut entities = vec![];
+    conflicts.archetypes.retain(|&id| {
+        let archetype = archetypes.get(id).unwrap();
+        let Some(first) = archetype.entities().first() else {
+            return true;
+        };
+        let present: Vec<_> = impls
+            .iter()
+            .filter(|(c, _)| archetype.contains(*c))
+            .copied()
+            .collect();
+        let names = || {
+            present
+                .iter()
+                .map(|(_, meta)| meta.type_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match policy {
+            ExclusivePolicy::Warn => {
+                warn!(
+                    "entity {:?} has multiple components implementing the exclusive trait `{}`: {}",
+                    first.entity(),
+                    std::any::type_name::<Trait>(),
+                    names(),
+                );
+                // Each archetype only gets reported once, so it doesn't need to be checked again.
+                false
+            }
+            ExclusivePolicy::Panic => panic!(
+                "entity {:?} has multiple components implementing the exclusive trait `{}`: {}",
+                first.entity(),
+                std::any::type_name::<Trait>(),
+                names(),
+            ),
+            ExclusivePolicy::RemoveOlder => {
+                entities.extend(
+                    archetype
+                        .entities()
+                        .iter()
+                        .map(|e| (e.entity(), present.clone())),
+                );
+                // Archetypes are never removed, so entities could be moved into this one again.
+                true
+            }
+        }
+    });
+
+    let this_run = world.read_change_tick();
+    for (entity, present) in entities {
+        let added: Vec<Tick> = present
+            .iter()
+            .map(|&(c, _)| {
+                let ticks = world.entity(entity).get_change_ticks_by_id(c).unwrap();
+                ticks.added_tick()
+            })
+            .collect();
+        // The impls are sorted by priority, so ties go to the first one.
+        let mut newest = 0;
+        for (i, &tick) in added.iter().enumerate().skip(1) {
+            if tick.is_newer_than(added[newest], this_run) {
+                newest = i;
+            }
+        }
+        let mut entity = world.entity_mut(entity);
+        for (i, (_, meta)) in present.iter().enumerate() {
+            if i != newest {
+                meta.remove(&mut entity);
+            }
+        }
+    }
+}
+
+/// Keeps track of the archetypes with multiple impls of an exclusive trait,
+/// for [`validate_exclusive_trait`].
+#[doc(hidden)]
+#[derive(Default)]
+pub struct ExclusiveConflicts {
+    /// The generation of the registry when these archetypes were checked.
+    generation: u32,
+    /// The number of archetypes that have been checked so far.
+    archetypes_seen: usize,
+    /// The archetypes with multiple impls that still need to be checked.
+    archetypes: Vec<ArchetypeId>,
+}
@@ -100,6 +100,15 @@
 //! # }
 //! ```
 //!
+//! If your components are reflected, you can instead mark the trait with `#[queryable(reflect)]`,
+//! add `#[reflect(Tooltip)]` to each component, and register all of them at once with
+//! [`register_traits_from_type_registry`](RegisterExt::register_traits_from_type_registry).
+//!
+//! Reflected components can also be queried as `dyn Reflect` after calling
+//! [`register_reflect_components`](RegisterExt::register_reflect_components).
+//! Since `Reflect` is a foreign trait, these queries have to be wrapped in [`All`] or [`One`],
+//! as in `Query<All<&dyn Reflect>>`; a plain `Query<&dyn Reflect>` does not compile.
+//!
 //! Unlike queries for concrete types, it's possible for an entity to have multiple components
 //! that match a trait query.
 //!
@@ -189,6 +198,10 @@
 //! # bevy::ecs::system::assert_is_system(show_tooltips);
 //! ```
 //!
+//! `One` skips entities that have more than one component implementing the trait.
+//! If you would rather get the impl with the highest
+//! [registration priority](RegisterExt::register_component_as_with_priority), use [`FirstImpl`] instead.
+//!
 //! # Performance
 //!
 //! The performance of trait queries is quite competitive. Here are some benchmarks for simple cases:
@@ -201,21 +214,241 @@
 //!
 use bevy::{
     ecs::{
-        component::{ComponentId, StorageType},
+        archetype::ArchetypeId,
+        component::{ComponentId, ComponentStorage, StorageType},
+        query::FilteredAccess,
         world::World,
     },
     prelude::*,
     ptr::{Ptr, PtrMut},
 };
+use std::any::{Any, TypeId};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Implements `WorldQuery` for a dense version of a trait query adapter.
+///
+/// The dense adapter behaves exactly like `$inner`, except that bevy is allowed
+/// to iterate over tables instead of archetypes. This is only sound if every
+/// impl of the trait is stored in a table, which is checked in `init_state`.
+macro_rules! impl_dense_query {
+    ($dense:ident, $inner:ident) => {
+        unsafe impl<Trait: ?Sized + TraitQuery> ReadOnlyWorldQuery for $dense<&Trait> {}
+
+        /// SAFETY: This accesses the same components as the non-dense adapter.
+        /// Since none of those components are stored in sparse sets,
+        /// every archetype containing them will have the same components in its table.
+        unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for $dense<&'a Trait> {
+            type Item<'w> = <$inner<&'a Trait> as WorldQuery>::Item<'w>;
+            type Fetch<'w> = <$inner<&'a Trait> as WorldQuery>::Fetch<'w>;
+            type ReadOnly = Self;
+            type State = TraitQueryState<Trait>;
+
+            #[inline]
+            fn shrink<'wlong: 'wshort, 'wshort>(
+                item: QueryItem<'wlong, Self>,
+            ) -> QueryItem<'wshort, Self> {
+                <$inner<&'a Trait> as WorldQuery>::shrink(item)
+            }
+
+            #[inline]
+            unsafe fn init_fetch<'w>(
+                world: UnsafeWorldCell<'w>,
+                state: &Self::State,
+                last_run: Tick,
+                this_run: Tick,
+            ) -> Self::Fetch<'w> {
+                <$inner<&'a Trait> as WorldQuery>::init_fetch(world, state, last_run, this_run)
+            }
+
+            #[inline]
+            unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+                <$inner<&'a Trait> as WorldQuery>::clone_fetch(fetch)
+            }
+
+            const IS_DENSE: bool = true;
+            const IS_ARCHETYPAL: bool = <$inner<&'a Trait> as WorldQuery>::IS_ARCHETYPAL;
+
+            #[inline]
+            unsafe fn set_archetype<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &Self::State,
+                archetype: &'w bevy::ecs::archetype::Archetype,
+                table: &'w bevy::ecs::storage::Table,
+            ) {
+                <$inner<&'a Trait> as WorldQuery>::set_archetype(fetch, state, archetype, table);
+            }
+
+            #[inline]
+            unsafe fn set_table<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &Self::State,
+                table: &'w bevy::ecs::storage::Table,
+            ) {
+                <$inner<&'a Trait> as WorldQuery>::set_table(fetch, state, table);
+            }
+
+            #[inline]
+            unsafe fn fetch<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                entity: Entity,
+                table_row: TableRow,
+            ) -> Self::Item<'w> {
+                <$inner<&'a Trait> as WorldQuery>::fetch(fetch, entity, table_row)
+            }
+
+            #[inline]
+            fn update_component_access(
+                state: &Self::State,
+                access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+            ) {
+                <$inner<&'a Trait> as WorldQuery>::update_component_access(state, access);
+            }
+
+            #[inline]
+            fn update_archetype_component_access(
+                state: &Self::State,
+                archetype: &bevy::ecs::archetype::Archetype,
+                access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+            ) {
+                <$inner<&'a Trait> as WorldQuery>::update_archetype_component_access(
+                    state, archetype, access,
+                );
+            }
+
+            #[inline]
+            fn init_state(world: &mut World) -> Self::State {
+                TraitQueryState::init_dense(world)
+            }
+
+            #[inline]
+            fn matches_component_set(
+                state: &Self::State,
+                set_contains_id: &impl Fn(ComponentId) -> bool,
+            ) -> bool {
+                <$inner<&'a Trait> as WorldQuery>::matches_component_set(state, set_contains_id)
+            }
+        }
+
+        /// SAFETY: This accesses the same components as the non-dense adapter.
+        /// Since none of those components are stored in sparse sets,
+        /// every archetype containing them will have the same components in its table.
+        unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for $dense<&'a mut Trait> {
+            type Item<'w> = <$inner<&'a mut Trait> as WorldQuery>::Item<'w>;
+            type Fetch<'w> = <$inner<&'a mut Trait> as WorldQuery>::Fetch<'w>;
+            type ReadOnly = $dense<&'a Trait>;
+            type State = TraitQueryState<Trait>;
+
+            #[inline]
+            fn shrink<'wlong: 'wshort, 'wshort>(
+                item: QueryItem<'wlong, Self>,
+            ) -> QueryItem<'wshort, Self> {
+                <$inner<&'a mut Trait> as WorldQuery>::shrink(item)
+            }
+
+            #[inline]
+            unsafe fn init_fetch<'w>(
+                world: UnsafeWorldCell<'w>,
+                state: &Self::State,
+                last_run: Tick,
+                this_run: Tick,
+            ) -> Self::Fetch<'w> {
+                <$inner<&'a mut Trait> as WorldQuery>::init_fetch(world, state, last_run, this_run)
+            }
+
+            #[inline]
+            unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+                <$inner<&'a mut Trait> as WorldQuery>::clone_fetch(fetch)
+            }
+
+            const IS_DENSE: bool = true;
+            const IS_ARCHETYPAL: bool = <$inner<&'a mut Trait> as WorldQuery>::IS_ARCHETYPAL;
+
+            #[inline]
+            unsafe fn set_archetype<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &Self::State,
+                archetype: &'w bevy::ecs::archetype::Archetype,
+                table: &'w bevy::ecs::storage::Table,
+            ) {
+                <$inner<&'a mut Trait> as WorldQuery>::set_archetype(
+                    fetch, state, archetype, table,
+                );
+            }
+
+            #[inline]
+            unsafe fn set_table<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &Self::State,
+                table: &'w bevy::ecs::storage::Table,
+            ) {
+                <$inner<&'a mut Trait> as WorldQuery>::set_table(fetch, state, table);
+            }
+
+            #[inline]
+            unsafe fn fetch<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                entity: Entity,
+                table_row: TableRow,
+            ) -> Self::Item<'w> {
+                <$inner<&'a mut Trait> as WorldQuery>::fetch(fetch, entity, table_row)
+            }
+
+            #[inline]
+            fn update_component_access(
+                state: &Self::State,
+                access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+            ) {
+                <$inner<&'a mut Trait> as WorldQuery>::update_component_access(state, access);
+            }
+
+            #[inline]
+            fn update_archetype_component_access(
+                state: &Self::State,
+                archetype: &bevy::ecs::archetype::Archetype,
+                access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+            ) {
+                <$inner<&'a mut Trait> as WorldQuery>::update_archetype_component_access(
+                    state, archetype, access,
+                );
+            }
+
+            #[inline]
+            fn init_state(world: &mut World) -> Self::State {
+                TraitQueryState::init_dense(world)
+            }
+
+            #[inline]
+            fn matches_component_set(
+                state: &Self::State,
+                set_contains_id: &impl Fn(ComponentId) -> bool,
+            ) -> bool {
+                <$inner<&'a mut Trait> as WorldQuery>::matches_component_set(state, set_contains_id)
+            }
+        }
+    };
+}
 
 #[cfg(test)]
 mod tests;
 
 pub mod all;
+pub mod commands;
+pub mod conditions;
+pub mod entity;
+pub mod exclusive;
 pub mod one;
+pub mod reflect;
+pub mod removed;
 
 pub use all::*;
+pub use commands::*;
+pub use conditions::*;
+pub use entity::*;
+pub use exclusive::*;
 pub use one::*;
+pub use reflect::*;
+pub use removed::*;
 
 /// Marker for traits that can be used in queries.
 pub trait TraitQuery: 'static {}
@@ -236,11 +469,161 @@ pub trait RegisterExt {
     /// Calling this multiple times with the same arguments will do nothing on subsequent calls.
     ///
     /// # Panics
-    /// If this function is called after the simulation starts for a given [`World`].
-    /// Due to engine limitations, registering new trait impls after the game starts cannot be supported.
+    /// If this function is called after the simulation starts for a given [`World`],
+    /// unless [`allow_late_registration`](Self::allow_late_registration) has been called for `Trait`.
     fn register_component_as<Trait: ?Sized + TraitQuery, C: Component>(&mut self) -> &mut Self
     where
         (C,): TraitQueryMarker<Trait, Covered = C>;
+
+    /// Allows a component to be used in trait queries, with the specified priority.
+    ///
+    /// When an entity has multiple components implementing `Trait`, they will be visited
+    /// in order of descending priority, regardless of how they are stored.
+    /// Components with the same priority are visited in the order they were registered.
+    /// Components registered with [`register_component_as`](Self::register_component_as) have a priority of 0.
+    ///
+    /// Calling this for a component that has already been registered with `Trait` will do nothing,
+    /// even if the priority is different.
+    ///
+    /// # Panics
+    /// Under the same conditions as [`register_component_as`](Self::register_component_as).
+    fn register_component_as_with_priority<Trait: ?Sized + TraitQuery, C: Component>(
+        &mut self,
+        priority: i32,
+    ) -> &mut Self
+    where
+        (C,): TraitQueryMarker<Trait, Covered = C>;
+
+    /// Allows a component to be used in trait queries, and to be copied to other entities
+    /// with [`TraitWorldExt::clone_traits`].
+    ///
+    /// If the component has already been registered with `Trait`, this makes it cloneable.
+    ///
+    /// # Panics
+    /// Under the same conditions as [`register_component_as`](Self::register_component_as).
+    fn register_cloneable_component_as<Trait: ?Sized + TraitQuery, C: Component + Clone>(
+        &mut self,
+    ) -> &mut Self
+    where
+        (C,): TraitQueryMarker<Trait, Covered = C>;
+
+    /// Registers every component in the [`AppTypeRegistry`] that has [`ReflectTraitQuery<Trait>`] type data,
+    /// as if by calling [`register_component_as`](Self::register_component_as) for each of them.
+    ///
+    /// Does nothing if the world does not have an [`AppTypeRegistry`].
+    ///
+    /// # Panics
+    /// Under the same conditions as [`register_component_as`](Self::register_component_as).
+    fn register_traits_from_type_registry<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self;
+
+    /// Registers every component in the [`AppTypeRegistry`] that reflects `Component`
+    /// with trait queries for `dyn Reflect`, so that they can be accessed without knowing their types.
+    ///
+    /// Since `Reflect` is a foreign trait, the query has to be wrapped in [`All`] or [`One`]:
+    ///
+    /// ```ignore
+    /// fn inspector(query: Query<All<&dyn Reflect>>) {
+    ///     for components in &query {
+    ///         for component in components {
+    ///             info!("{}", component.type_name());
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Only components that have already been added to the world, or initialized with
+    /// [`World::init_component`], are registered, in order of their type names.
+    /// Calling this again registers the components that were initialized since,
+    /// which panics if queries for `dyn Reflect` have been created in the meantime
+    /// unless [`allow_late_registration`](Self::allow_late_registration) has been called for it.
+    /// Does nothing if the world does not have an [`AppTypeRegistry`].
+    ///
+    /// # Panics
+    /// Under the same conditions as [`register_component_as`](Self::register_component_as).
+    fn register_reflect_components(&mut self) -> &mut Self;
+
+    /// Allows a component to be used in trait queries,
+    /// returning an error instead of panicking if the component can't be registered.
+    fn try_register_component_as<Trait: ?Sized + TraitQuery, C: Component>(
+        &mut self,
+    ) -> Result<RegistrationOutcome, TraitRegistrationError>
+    where
+        (C,): TraitQueryMarker<Trait, Covered = C>;
+
+    /// Allows components to be registered with `Trait` after trait queries for it have been created,
+    /// but only query states created afterwards will pick them up.
+    ///
+    /// Due to engine limitations, the world access of a query can't change after it has been created,
+    /// so existing queries and systems will keep ignoring impls registered after they were initialized,
+    /// and log a warning the first time they match new archetypes afterwards.
+    /// Queries and systems that get initialized later will see every registered impl.
+    ///
+    /// Systems can't be initialized again, so they only see the impls that were registered before
+    /// they first ran. To pick up impls registered at any time, use a [`QueryState`] instead,
+    /// and create it again whenever the [generation](TraitRegistry::generation) of the registry changes:
+    ///
+    /// ```ignore
+    /// fn show_tooltips(world: &mut World, mut cache: Local<Option<(u32, QueryState<&dyn Tooltip>)>>) {
+    ///     let generation = world.trait_registry::<dyn Tooltip>().map_or(0, |r| r.generation());
+    ///     if !matches!(&*cache, Some((cached, _)) if *cached == generation) {
+    ///         *cache = Some((generation, world.query()));
+    ///     }
+    ///     let (_, tooltips) = cache.as_mut().unwrap();
+    ///     for tooltip in tooltips.iter(world).flatten() {
+    ///         info!("{}", tooltip.tooltip());
+    ///     }
+    /// }
+    /// ```
+    fn allow_late_registration<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self;
+
+    /// Makes [`One`] queries for `Trait`, and the other adapters that require exactly one impl
+    /// such as [`WithOneTrait`] and [`OneChanged`], log a warning when they skip entities
+    /// because they have more than one component implementing `Trait`.
+    /// Each combination of components is only reported once.
+    ///
+    /// This only affects queries created after it is called.
+    /// See also [`TraitQueryState::skipped_archetypes`].
+    fn warn_on_multiple_impls<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self;
+
+    /// Validates that each entity has at most one component implementing `Trait`.
+    ///
+    /// This is not enforced when components get inserted, including through helpers such as
+    /// [`TraitEntityCommandsExt`] and [`TraitWorldExt`]. Instead, entities with more than one of these
+    /// components are handled according to `policy` whenever the [`validate_exclusive_trait`] system runs.
+    /// For [`App`], this system gets added to the [`PostUpdate`] schedule, so until then,
+    /// queries in that frame's [`Update`] schedule and earlier can see entities with several impls.
+    /// To run it elsewhere, call this on the [`World`] instead and add the system yourself:
+    ///
+    /// ```ignore
+    /// app.world
+    ///     .validate_exclusive_trait::<dyn Tooltip>(ExclusivePolicy::RemoveOlder);
+    /// app.add_systems(
+    ///     Update,
+    ///     (spawn_units, apply_deferred, validate_exclusive_trait::<dyn Tooltip>).chain(),
+    /// );
+    /// ```
+    fn validate_exclusive_trait<Trait: ?Sized + TraitQuery>(
+        &mut self,
+        policy: ExclusivePolicy,
+    ) -> &mut Self;
+
+    /// Prevents a component from being used in trait queries for `Trait`.
+    /// Does nothing if the component has not been registered with `Trait`.
+    ///
+    /// Queries created before this is called have access to the component,
+    /// so they will keep fetching it; only queries created afterwards will ignore it.
+    fn unregister_component_as<Trait: ?Sized + TraitQuery, C: Component>(&mut self) -> &mut Self;
+
+    /// Unregisters every component from `Trait`, and allows new components to be registered
+    /// as if no queries for `Trait` had been created.
+    ///
+    /// As with [`unregister_component_as`](Self::unregister_component_as), queries created before
+    /// this is called will keep fetching the components that were registered at the time.
+    fn reset_trait_registry<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self;
+
+    /// Returns a read-only view of the components that have been registered with `Trait`,
+    /// or `None` if nothing has been registered with it yet.
+    fn trait_registry<Trait: ?Sized + TraitQuery>(&self) -> Option<TraitRegistry<'_, Trait>>;
 }
 
 impl RegisterExt for World {
@@ -248,17 +631,143 @@ impl RegisterExt for World {
     where
         (C,): TraitQueryMarker<Trait, Covered = C>,
     {
-        let component_id = self.init_component::<C>();
-        let registry = self
-            .get_resource_or_insert_with::<TraitImplRegistry<Trait>>(default)
-            .into_inner();
-        let meta = TraitImplMeta {
-            size_bytes: std::mem::size_of::<C>(),
-            dyn_ctor: DynCtor { cast: <(C,)>::cast },
+        self.register_component_as_with_priority::<Trait, C>(0)
+    }
+
+    fn register_component_as_with_priority<Trait: ?Sized + TraitQuery, C: Component>(
+        &mut self,
+        priority: i32,
+    ) -> &mut Self
+    where
+        (C,): TraitQueryMarker<Trait, Covered = C>,
+    {
+        if let Err(err) = register_impl::<Trait, C>(self, priority) {
+            panic!("{err}");
+        }
+        self
+    }
+
+    fn register_cloneable_component_as<Trait: ?Sized + TraitQuery, C: Component + Clone>(
+        &mut self,
+    ) -> &mut Self
+    where
+        (C,): TraitQueryMarker<Trait, Covered = C>,
+    {
+        self.register_component_as::<Trait, C>();
+        let component = self.init_component::<C>();
+        self.resource_mut::<TraitImplRegistry<Trait>>()
+            .set_clone_into(component, |world, source, target| {
+                if let Some(component) = world.get::<C>(source).cloned() {
+                    world.entity_mut(target).insert(component);
+                }
+            });
+        self
+    }
+
+    fn register_traits_from_type_registry<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self {
+        let Some(type_registry) = self.get_resource::<AppTypeRegistry>() else {
+            return self;
         };
-        registry.register::<C>(component_id, meta);
+        let impls: Vec<_> = type_registry
+            .read()
+            .iter()
+            .filter_map(|registration| registration.data::<ReflectTraitQuery<Trait>>())
+            .cloned()
+            .collect();
+        for data in impls {
+            data.register(self);
+        }
+        self
+    }
+
+    fn register_reflect_components(&mut self) -> &mut Self {
+        reflect::register_reflect_components(self)
+    }
+
+    fn try_register_component_as<Trait: ?Sized + TraitQuery, C: Component>(
+        &mut self,
+    ) -> Result<RegistrationOutcome, TraitRegistrationError>
+    where
+        (C,): TraitQueryMarker<Trait, Covered = C>,
+    {
+        register_impl::<Trait, C>(self, 0)
+    }
+
+    fn allow_late_registration<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self {
+        self.get_resource_or_insert_with::<TraitImplRegistry<Trait>>(default)
+            .late_registration = true;
+        self
+    }
+
+    fn warn_on_multiple_impls<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self {
+        self.get_resource_or_insert_with::<TraitImplRegistry<Trait>>(default)
+            .reported_conflicts
+            .get_or_insert_with(default);
         self
     }
+
+    fn validate_exclusive_trait<Trait: ?Sized + TraitQuery>(
+        &mut self,
+        policy: ExclusivePolicy,
+    ) -> &mut Self {
+        self.get_resource_or_insert_with::<TraitImplRegistry<Trait>>(default)
+            .exclusive = Some(policy);
+        self
+    }
+
+    fn unregister_component_as<Trait: ?Sized + TraitQuery, C: Component>(&mut self) -> &mut Self {
+        if let Some(component) = self.component_id::<C>() {
+            if let Some(mut registry) = self.get_resource_mut::<TraitImplRegistry<Trait>>() {
+                registry.retain(|c| c != component);
+            }
+        }
+        self
+    }
+
+    fn reset_trait_registry<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self {
+        if let Some(mut registry) = self.get_resource_mut::<TraitImplRegistry<Trait>>() {
+            registry.retain(|_| false);
+            registry.sealed = false;
+        }
+        self
+    }
+
+    fn trait_registry<Trait: ?Sized + TraitQuery>(&self) -> Option<TraitRegistry<'_, Trait>> {
+        let registry = self.get_resource::<TraitImplRegistry<Trait>>()?;
+        Some(TraitRegistry { registry })
+    }
+}
+
+fn register_impl<Trait: ?Sized + TraitQuery, C: Component>(
+    world: &mut World,
+    priority: i32,
+) -> Result<RegistrationOutcome, TraitRegistrationError>
+where
+    (C,): TraitQueryMarker<Trait, Covered = C>,
+{
+    let component_id = world.init_component::<C>();
+    let registry = world
+        .get_resource_or_insert_with::<TraitImplRegistry<Trait>>(default)
+        .into_inner();
+    let meta = TraitImplMeta {
+        priority,
+        // Assigned by the registry.
+        order: 0,
+        size_bytes: std::mem::size_of::<C>(),
+        storage_type: <C as Component>::Storage::STORAGE_TYPE,
+        type_id: TypeId::of::<C>(),
+        type_name: std::any::type_name::<C>(),
+        dyn_ctor: DynCtor {
+            cast: |ptr, _| <(C,)>::cast(ptr),
+            cast_mut: |ptr, _| <(C,)>::cast(ptr),
+            data: std::ptr::null(),
+        },
+        remove: |entity, _| {
+            entity.remove::<C>();
+        },
+        clone_into: None,
+    };
+    registry.register(component_id, meta)
 }
 
 impl RegisterExt for App {
@@ -269,65 +778,280 @@ impl RegisterExt for App {
         self.world.register_component_as::<Trait, C>();
         self
     }
+
+    fn register_component_as_with_priority<Trait: ?Sized + TraitQuery, C: Component>(
+        &mut self,
+        priority: i32,
+    ) -> &mut Self
+    where
+        (C,): TraitQueryMarker<Trait, Covered = C>,
+    {
+        self.world
+            .register_component_as_with_priority::<Trait, C>(priority);
+        self
+    }
+
+    fn register_cloneable_component_as<Trait: ?Sized + TraitQuery, C: Component + Clone>(
+        &mut self,
+    ) -> &mut Self
+    where
+        (C,): TraitQueryMarker<Trait, Covered = C>,
+    {
+        self.world.register_cloneable_component_as::<Trait, C>();
+        self
+    }
+
+    fn register_traits_from_type_registry<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self {
+        self.world.register_traits_from_type_registry::<Trait>();
+        self
+    }
+
+    fn register_reflect_components(&mut self) -> &mut Self {
+        self.world.register_reflect_components();
+        self
+    }
+
+    fn try_register_component_as<Trait: ?Sized + TraitQuery, C: Component>(
+        &mut self,
+    ) -> Result<RegistrationOutcome, TraitRegistrationError>
+    where
+        (C,): TraitQueryMarker<Trait, Covered = C>,
+    {
+        self.world.try_register_component_as::<Trait, C>()
+    }
+
+    fn allow_late_registration<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self {
+        self.world.allow_late_registration::<Trait>();
+        self
+    }
+
+    fn warn_on_multiple_impls<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self {
+        self.world.warn_on_multiple_impls::<Trait>();
+        self
+    }
+
+    fn validate_exclusive_trait<Trait: ?Sized + TraitQuery>(
+        &mut self,
+        policy: ExclusivePolicy,
+    ) -> &mut Self {
+        let already_exclusive = self
+            .world
+            .get_resource::<TraitImplRegistry<Trait>>()
+            .is_some_and(|registry| registry.exclusive.is_some());
+        self.world.validate_exclusive_trait::<Trait>(policy);
+        if !already_exclusive {
+            self.add_systems(PostUpdate, validate_exclusive_trait::<Trait>);
+        }
+        self
+    }
+
+    fn unregister_component_as<Trait: ?Sized + TraitQuery, C: Component>(&mut self) -> &mut Self {
+        self.world.unregister_component_as::<Trait, C>();
+        self
+    }
+
+    fn reset_trait_registry<Trait: ?Sized + TraitQuery>(&mut self) -> &mut Self {
+        self.world.reset_trait_registry::<Trait>();
+        self
+    }
+
+    fn trait_registry<Trait: ?Sized + TraitQuery>(&self) -> Option<TraitRegistry<'_, Trait>> {
+        self.world.trait_registry::<Trait>()
+    }
+}
+
+/// The result of successfully registering a component with a trait.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegistrationOutcome {
+    /// The component was registered with the trait.
+    Registered,
+    /// The component had already been registered with the trait, so nothing happened.
+    AlreadyRegistered,
+}
+
+/// An error that prevented a component from being registered with a trait.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraitRegistrationError {
+    /// Queries for the trait have already been created, and late registration
+    /// has not been enabled with [`RegisterExt::allow_late_registration`].
+    Sealed,
 }
 
+impl std::fmt::Display for TraitRegistrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sealed => write!(
+                f,
+                "Cannot register new trait impls after the game has started"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TraitRegistrationError {}
+
 #[derive(Resource)]
 struct TraitImplRegistry<Trait: ?Sized> {
+    /// The registered impls.
+    /// Query states share the version of these lists that was current when they were created.
+    lists: Arc<ImplLists<Trait>>,
+
+    sealed: bool,
+    /// If true, new impls can be registered after the registry has been sealed.
+    late_registration: bool,
+    /// Incremented every time an impl gets registered or unregistered.
+    generation: u32,
+
+    /// Previous versions of `lists` that were still shared with query states when they got replaced.
+    /// Those query states only have access to the components in their version, and the trait objects
+    /// they fetch can outlive them, so the registry keeps each version alive until it is no longer shared.
+    retired: Vec<Arc<ImplLists<Trait>>>,
+
+    /// If set, `One` queries log a warning when they skip an archetype with multiple impls.
+    /// This holds the combinations of impls that have already been reported.
+    reported_conflicts: Option<ReportedConflicts>,
+
+    /// If set, entities should have at most one impl of this trait.
+    exclusive: Option<ExclusivePolicy>,
+}
+
+type ReportedConflicts = Arc<Mutex<Vec<Box<[ComponentId]>>>>;
+
+/// The impls of a trait, sorted by priority.
+struct ImplLists<Trait: ?Sized> {
     // Component IDs are stored contiguously so that we can search them quickly.
     components: Vec<ComponentId>,
     meta: Vec<TraitImplMeta<Trait>>,
+    /// Data referenced by the [`DynCtor`] of these impls, which is kept alive as long as the lists are.
+    type_data: Vec<Arc<dyn Any + Send + Sync>>,
+    /// Set once impls have been registered or unregistered since these lists were shared with query states.
+    outdated: AtomicBool,
+}
 
-    table_components: Vec<ComponentId>,
-    table_meta: Vec<TraitImplMeta<Trait>>,
-
-    sparse_components: Vec<ComponentId>,
-    sparse_meta: Vec<TraitImplMeta<Trait>>,
+impl<T: ?Sized> Clone for ImplLists<T> {
+    fn clone(&self) -> Self {
+        Self {
+            components: self.components.clone(),
+            meta: self.meta.clone(),
+            type_data: self.type_data.clone(),
+            outdated: AtomicBool::new(false),
+        }
+    }
+}
 
-    sealed: bool,
+impl<Trait: ?Sized> ImplLists<Trait> {
+    /// Returns the ID of the component with the given type, if it has been registered with this trait.
+    #[inline]
+    fn component_of<C: 'static>(&self) -> Option<ComponentId> {
+        let type_id = TypeId::of::<C>();
+        let index = self.meta.iter().position(|meta| meta.type_id == type_id)?;
+        Some(self.components[index])
+    }
 }
 
 impl<T: ?Sized> Default for TraitImplRegistry<T> {
     #[inline]
     fn default() -> Self {
         Self {
-            components: vec![],
-            meta: vec![],
-            table_components: vec![],
-            table_meta: vec![],
-            sparse_components: vec![],
-            sparse_meta: vec![],
+            lists: Arc::new(ImplLists {
+                components: vec![],
+                meta: vec![],
+                type_data: vec![],
+                outdated: AtomicBool::new(false),
+            }),
             sealed: false,
+            late_registration: false,
+            generation: 0,
+            retired: vec![],
+            reported_conflicts: None,
+            exclusive: None,
         }
     }
 }
 
 impl<Trait: ?Sized + TraitQuery> TraitImplRegistry<Trait> {
-    fn register<C: Component>(&mut self, component: ComponentId, meta: TraitImplMeta<Trait>) {
+    /// Returns the current lists for modification.
+    /// If any query states are using them, they get retired and replaced with a copy.
+    fn lists_mut(&mut self) -> &mut ImplLists<Trait> {
+        // Reclaim the versions that are no longer used by any query state.
+        self.retired.retain(|lists| Arc::strong_count(lists) > 1);
+        if Arc::strong_count(&self.lists) > 1 {
+            self.retired.push(self.lists.clone());
+        }
+        Arc::make_mut(&mut self.lists)
+    }
+
+    /// Like [`Self::lists_mut`], but for changing which impls are registered.
+    /// This lets the query states using the current lists know that they are outdated.
+    fn update_lists(&mut self) -> &mut ImplLists<Trait> {
+        self.generation += 1;
+        self.lists.outdated.store(true, Ordering::Relaxed);
+        let lists = self.lists_mut();
+        *lists.outdated.get_mut() = false;
+        lists
+    }
+
+    fn register(
+        &mut self,
+        component: ComponentId,
+        meta: TraitImplMeta<Trait>,
+    ) -> Result<RegistrationOutcome, TraitRegistrationError> {
         // Don't register the same component multiple times.
-        if self.components.contains(&component) {
-            return;
+        if self.lists.components.contains(&component) {
+            return Ok(RegistrationOutcome::AlreadyRegistered);
         }
 
-        if self.sealed {
+        if self.sealed && !self.late_registration {
             // It is not possible to update the `FetchState` for a given system after the game has started,
-            // so for explicitness, let's panic instead of having a trait impl silently get forgotten.
-            panic!("Cannot register new trait impls after the game has started");
+            // so for explicitness, let's report an error instead of having a trait impl silently get forgotten.
+            return Err(TraitRegistrationError::Sealed);
         }
 
-        self.components.push(component);
-        self.meta.push(meta);
+        let order = self.generation + 1;
+        let meta = TraitImplMeta { order, ..meta };
 
-        use bevy::ecs::component::ComponentStorage;
-        match <C as Component>::Storage::STORAGE_TYPE {
-            StorageType::Table => {
-                self.table_components.push(component);
-                self.table_meta.push(meta);
-            }
-            StorageType::SparseSet => {
-                self.sparse_components.push(component);
-                self.sparse_meta.push(meta);
-            }
+        let lists = self.update_lists();
+        let index = lists.meta.partition_point(|other| !meta.precedes(other));
+        lists.components.insert(index, component);
+        lists.meta.insert(index, meta);
+
+        Ok(RegistrationOutcome::Registered)
+    }
+
+    /// Keeps `data` alive for as long as any impl can reference it through [`DynCtor::data`].
+    fn keep_type_data(&mut self, data: Arc<dyn Any + Send + Sync>) {
+        self.lists_mut().type_data.push(data);
+    }
+
+    /// Sets the function used to clone `component` from one entity to another.
+    fn set_clone_into(&mut self, component: ComponentId, clone_into: CloneInto) {
+        let lists = self.lists_mut();
+        if let Some(index) = lists.components.iter().position(|&c| c == component) {
+            lists.meta[index].clone_into = Some(clone_into);
+        }
+    }
+
+    /// Removes every impl for which `keep` returns false.
+    fn retain(&mut self, mut keep: impl FnMut(ComponentId) -> bool) {
+        fn filter<T: ?Sized>(
+            components: &[ComponentId],
+            meta: &[TraitImplMeta<T>],
+            keep: &mut impl FnMut(ComponentId) -> bool,
+        ) -> (Vec<ComponentId>, Vec<TraitImplMeta<T>>) {
+            std::iter::zip(components, meta)
+                .filter(|(&component, _)| keep(component))
+                .unzip()
+        }
+
+        if self.lists.components.iter().all(|&c| keep(c)) {
+            return;
         }
+        let lists = self.update_lists();
+        (lists.components, lists.meta) = filter(&lists.components, &lists.meta, &mut keep);
+        lists.type_data.retain(|data| {
+            let data = Arc::as_ptr(data).cast::<()>();
+            lists.meta.iter().any(|meta| meta.dyn_ctor.data == data)
+        });
     }
 
     fn seal(&mut self) {
@@ -337,10 +1061,24 @@ impl<Trait: ?Sized + TraitQuery> TraitImplRegistry<Trait> {
 
 /// Stores data about an impl of a trait
 struct TraitImplMeta<Trait: ?Sized> {
+    /// Impls with a higher priority are visited first.
+    priority: i32,
+    /// Breaks ties between impls with the same priority, in the order they were registered.
+    order: u32,
     size_bytes: usize,
+    storage_type: StorageType,
+    type_id: TypeId,
+    type_name: &'static str,
     dyn_ctor: DynCtor<Trait>,
+    /// Removes this component from an entity. This gets passed the same data as `dyn_ctor`.
+    remove: fn(&mut bevy::ecs::world::EntityMut, *const ()),
+    /// Clones this component from one entity to another, if it was registered as cloneable.
+    clone_into: Option<CloneInto>,
 }
 
+/// Clones a component from the first entity and inserts it into the second entity.
+type CloneInto = fn(&mut World, Entity, Entity);
+
 impl<T: ?Sized> Copy for TraitImplMeta<T> {}
 impl<T: ?Sized> Clone for TraitImplMeta<T> {
     fn clone(&self) -> Self {
@@ -348,6 +1086,126 @@ impl<T: ?Sized> Clone for TraitImplMeta<T> {
     }
 }
 
+impl<T: ?Sized> TraitImplMeta<T> {
+    /// Returns `true` if this impl should be visited before `other`.
+    #[inline]
+    fn precedes(&self, other: &Self) -> bool {
+        (self.priority, other.order) > (other.priority, self.order)
+    }
+
+    /// Removes this component from `entity`.
+    fn remove(&self, entity: &mut bevy::ecs::world::EntityMut) {
+        (self.remove)(entity, self.dyn_ctor.data);
+    }
+
+    #[inline]
+    fn info(&self, component_id: ComponentId) -> TraitImplInfo {
+        TraitImplInfo {
+            component_id,
+            type_id: self.type_id,
+            type_name: self.type_name,
+            storage_type: self.storage_type,
+            size_bytes: self.size_bytes,
+        }
+    }
+}
+
+/// Describes a component type that has been registered as an implementation of a trait.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraitImplInfo {
+    component_id: ComponentId,
+    type_id: TypeId,
+    type_name: &'static str,
+    storage_type: StorageType,
+    size_bytes: usize,
+}
+
+impl TraitImplInfo {
+    /// The [`ComponentId`] of the component implementing the trait.
+    #[inline]
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+    /// The [`TypeId`] of the component implementing the trait.
+    #[inline]
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+    /// The name of the component type implementing the trait.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+    /// How the component implementing the trait is stored in the world.
+    #[inline]
+    pub fn storage_type(&self) -> StorageType {
+        self.storage_type
+    }
+    /// The size of the component implementing the trait, in bytes.
+    #[inline]
+    pub fn size_bytes(&self) -> usize {
+        self.size_bytes
+    }
+}
+
+/// Read-only view of the components that have been registered with a trait.
+///
+/// This can be obtained with [`RegisterExt::trait_registry`].
+pub struct TraitRegistry<'a, Trait: ?Sized> {
+    registry: &'a TraitImplRegistry<Trait>,
+}
+
+impl<'a, Trait: ?Sized> TraitRegistry<'a, Trait> {
+    /// The IDs of every component registered with the trait,
+    /// in priority order, ties broken by registration order.
+    #[inline]
+    pub fn component_ids(&self) -> &'a [ComponentId] {
+        &self.registry.lists.components
+    }
+    /// Returns an iterator describing every component registered with the trait,
+    /// in priority order, ties broken by registration order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = TraitImplInfo> + 'a {
+        std::iter::zip(&self.registry.lists.components, &self.registry.lists.meta)
+            .map(|(&component, meta)| meta.info(component))
+    }
+    /// Returns a description of the component with the given ID,
+    /// or `None` if it has not been registered with the trait.
+    pub fn get(&self, component: ComponentId) -> Option<TraitImplInfo> {
+        let index = (self.registry.lists.components.iter()).position(|&c| c == component)?;
+        Some(self.registry.lists.meta[index].info(component))
+    }
+    /// Returns `true` if the component with the given ID has been registered with the trait.
+    #[inline]
+    pub fn contains(&self, component: ComponentId) -> bool {
+        self.registry.lists.components.contains(&component)
+    }
+    /// The number of components registered with the trait.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.registry.lists.components.len()
+    }
+    /// Returns `true` if no components have been registered with the trait.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.registry.lists.components.is_empty()
+    }
+    /// Returns `true` if trait queries have been created for the trait,
+    /// which prevents new components from being registered unless late registration is allowed.
+    #[inline]
+    pub fn is_sealed(&self) -> bool {
+        self.registry.sealed
+    }
+    /// Changes whenever a component is registered with or unregistered from the trait.
+    ///
+    /// Query states only see the impls that were registered when they were created, so this
+    /// can be used to tell when they need to be created again.
+    /// See [`RegisterExt::allow_late_registration`].
+    #[inline]
+    pub fn generation(&self) -> u32 {
+        self.registry.generation
+    }
+}
+
 #[doc(hidden)]
 pub mod imports {
     pub use bevy::ecs::{
@@ -363,10 +1221,17 @@ pub mod imports {
     };
 }
 
-#[doc(hidden)]
+/// The [`WorldQuery::State`](bevy::ecs::query::WorldQuery::State) of the trait query adapters,
+/// holding the impls that were registered when it was created.
 pub struct TraitQueryState<Trait: ?Sized> {
     components: Box<[ComponentId]>,
     meta: Box<[TraitImplMeta<Trait>]>,
+    /// The lists of the registry when this state was created.
+    /// The registry keeps these alive until this state is dropped.
+    lists: Arc<ImplLists<Trait>>,
+    warned_stale: AtomicBool,
+    /// Shared with the registry, if `One` queries should warn about skipped archetypes.
+    reported_conflicts: Option<ReportedConflicts>,
 }
 
 impl<Trait: ?Sized + TraitQuery> TraitQueryState<Trait> {
@@ -383,33 +1248,194 @@ impl<Trait: ?Sized + TraitQuery> TraitQueryState<Trait> {
         let mut registry = world.get_resource_or_insert_with(missing_registry);
         registry.seal();
         Self {
-            components: registry.components.clone().into_boxed_slice(),
-            meta: registry.meta.clone().into_boxed_slice(),
+            components: registry.lists.components.clone().into_boxed_slice(),
+            meta: registry.lists.meta.clone().into_boxed_slice(),
+            lists: registry.lists.clone(),
+            warned_stale: AtomicBool::new(false),
+            reported_conflicts: registry.reported_conflicts.clone(),
         }
     }
 
+    /// Returns the components implementing `Trait` that this state knows about, in priority order.
+    pub fn components(&self) -> &[ComponentId] {
+        &self.components
+    }
+
+    /// Returns the archetypes that [`One`] queries with this state skip because they have
+    /// more than one component implementing `Trait`, out of the impls known to this state.
+    ///
+    /// Only the trait impls are considered, not any other part of the query.
+    /// To log a warning whenever a new archetype gets skipped, see
+    /// [`RegisterExt::warn_on_multiple_impls`].
+    ///
+    /// ```ignore
+    /// let state = <One<&dyn Tooltip> as WorldQuery>::init_state(&mut world);
+    /// for archetype in state.skipped_archetypes(&world) {
+    ///     warn!("entities in {archetype:?} have several tooltips");
+    /// }
+    /// ```
+    pub fn skipped_archetypes(&self, world: &World) -> Vec<ArchetypeId> {
+        (world.archetypes().iter())
+            .filter(|archetype| {
+                let mut present = self.components.iter().filter(|&&c| archetype.contains(c));
+                present.nth(1).is_some()
+            })
+            .map(|archetype| archetype.id())
+            .collect()
+    }
+
+    /// Returns the impls that are known to this state, for as long as `registry` is borrowed.
+    #[inline]
+    fn registered_impls<'a>(
+        &self,
+        _registry: &'a TraitImplRegistry<Trait>,
+    ) -> &'a ImplLists<Trait> {
+        // SAFETY: The impls in these lists are never modified once they have been shared. The registry holds
+        // a reference to them for as long as this state exists, and it only lets go of them once they are
+        // no longer shared, which requires mutable access to the registry.
+        // So they will live for as long as it is borrowed.
+        unsafe { &*Arc::as_ptr(&self.lists) }
+    }
+
+    /// Logs a warning the first time this state matches archetypes after impls have been registered
+    /// or unregistered, since this query will keep using the impls that were registered when it was created.
+    #[inline]
+    fn warn_if_stale(&self) {
+        #[cold]
+        fn warn_stale<T: ?Sized + 'static>() {
+            warn!(
+                "the impls of `{}` have changed since a query for it was created; \
+                it will keep using the impls that were registered at the time \
+                until its query state is created again",
+                std::any::type_name::<T>()
+            );
+        }
+
+        if self.lists.outdated.load(Ordering::Relaxed)
+            && !self.warned_stale.swap(true, Ordering::Relaxed)
+        {
+            warn_stale::<Trait>();
+        }
+    }
+
+    /// Like [`Self::init`], but panics if any of the registered components are stored in sparse sets,
+    /// since those can't be accessed when iterating over tables.
+    fn init_dense(world: &mut World) -> Self {
+        let state = Self::init(world);
+        let registry = world.resource::<TraitImplRegistry<Trait>>();
+        let sparse =
+            (registry.lists.meta.iter()).find(|meta| meta.storage_type == StorageType::SparseSet);
+        if let Some(meta) = sparse {
+            dense_registry_error::<Trait>(meta.type_name);
+        }
+        state
+    }
+
     #[inline]
     fn matches_component_set_any(&self, set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
+        self.warn_if_stale();
         self.components.iter().copied().any(set_contains_id)
     }
 
     #[inline]
     fn matches_component_set_one(&self, set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
-        let match_count = self
-            .components
+        self.warn_if_stale();
+        let count = self.count_component_set(set_contains_id);
+        if count > 1 {
+            if let Some(reported) = &self.reported_conflicts {
+                self.report_conflict(reported, set_contains_id);
+            }
+        }
+        count == 1
+    }
+
+    /// Logs a warning about an archetype with multiple impls, unless its impls have already been reported.
+    #[cold]
+    fn report_conflict(
+        &self,
+        reported: &ReportedConflicts,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) {
+        let (components, names): (Vec<_>, Vec<_>) = std::iter::zip(&*self.components, &*self.meta)
+            .filter(|(&component, _)| set_contains_id(component))
+            .map(|(&component, meta)| (component, meta.type_name))
+            .unzip();
+        let mut reported = reported.lock().unwrap_or_else(PoisonError::into_inner);
+        if !reported.iter().any(|c| **c == *components) {
+            warn!(
+                "Queries requiring exactly one impl of `{}` are skipping entities with multiple impls: {}",
+                std::any::type_name::<Trait>(),
+                names.join(", ")
+            );
+            reported.push(components.into_boxed_slice());
+        }
+    }
+
+    #[inline]
+    fn count_component_set(&self, set_contains_id: &impl Fn(ComponentId) -> bool) -> usize {
+        self.components
             .iter()
             .filter(|&&c| set_contains_id(c))
-            .count();
-        match_count == 1
+            .count()
+    }
+
+    /// Restricts `access` to entities with at least one of the registered components.
+    /// This is expressed as a disjunction of `With` filters.
+    fn and_with_any(&self, access: &mut FilteredAccess<ComponentId>) {
+        let mut new_access: Option<FilteredAccess<ComponentId>> = None;
+        for &component in &*self.components {
+            let mut intermediate = access.clone();
+            intermediate.and_with(component);
+            match &mut new_access {
+                Some(new_access) => new_access.append_or(&intermediate),
+                None => new_access = Some(intermediate),
+            }
+        }
+        if let Some(new_access) = new_access {
+            *access = new_access;
+        }
+    }
+
+    /// Restricts `access` to entities with exactly one of the registered components.
+    /// This is expressed as a disjunction of filters like `(With<A>, Without<B>, Without<C>, ...)`.
+    fn and_with_one(&self, access: &mut FilteredAccess<ComponentId>) {
+        let mut new_access: Option<FilteredAccess<ComponentId>> = None;
+        for &component in &*self.components {
+            let mut intermediate = access.clone();
+            intermediate.and_with(component);
+            for &other in &*self.components {
+                if other != component {
+                    intermediate.and_without(other);
+                }
+            }
+            match &mut new_access {
+                Some(new_access) => new_access.append_or(&intermediate),
+                None => new_access = Some(intermediate),
+            }
+        }
+        if let Some(new_access) = new_access {
+            *access = new_access;
+        }
     }
 }
 
 /// Turns an untyped pointer into a trait object pointer,
 /// for a specific erased concrete type.
 struct DynCtor<Trait: ?Sized> {
-    cast: unsafe fn(*mut u8) -> *mut Trait,
+    /// Used for shared access. The returned pointer must only be dereferenced immutably.
+    cast: unsafe fn(*mut u8, *const ()) -> *mut Trait,
+    /// Used for exclusive access.
+    cast_mut: unsafe fn(*mut u8, *const ()) -> *mut Trait,
+    /// Extra data passed to `cast` and `cast_mut`, for impls that were registered without knowing the concrete type.
+    /// This is null for impls registered with a concrete type, and otherwise points to data
+    /// owned by every [`ImplLists`] that contains the impl.
+    data: *const (),
 }
 
+// SAFETY: `data` is either null or points to immutable data that is `Send` and `Sync`.
+unsafe impl<T: ?Sized> Send for DynCtor<T> {}
+unsafe impl<T: ?Sized> Sync for DynCtor<T> {}
+
 impl<T: ?Sized> Copy for DynCtor<T> {}
 impl<T: ?Sized> Clone for DynCtor<T> {
     fn clone(&self) -> Self {
@@ -419,12 +1445,12 @@ impl<T: ?Sized> Clone for DynCtor<T> {
 
 impl<Trait: ?Sized> DynCtor<Trait> {
     #[inline]
-    unsafe fn cast(self, ptr: Ptr) -> &Trait {
-        &*(self.cast)(ptr.as_ptr())
+    unsafe fn cast(self, ptr: Ptr<'_>) -> &Trait {
+        &*(self.cast)(ptr.as_ptr(), self.data)
     }
     #[inline]
-    unsafe fn cast_mut(self, ptr: PtrMut) -> &mut Trait {
-        &mut *(self.cast)(ptr.as_ptr())
+    unsafe fn cast_mut(self, ptr: PtrMut<'_>) -> &mut Trait {
+        &mut *(self.cast_mut)(ptr.as_ptr(), self.data)
     }
 }
 
@@ -479,3 +1505,12 @@ unsafe fn debug_unreachable() -> ! {
 fn trait_registry_error() -> ! {
     panic!("The trait query registry has not been initialized; did you forget to register your traits with the world?")
 }
+
+#[inline(never)]
+#[cold]
+fn dense_registry_error<Trait: ?Sized + 'static>(component: &str) -> ! {
+    panic!(
+        "Dense trait queries require every component implementing `{}` to be stored in a table, but `{component}` is stored in a sparse set",
+        std::any::type_name::<Trait>(),
+    )
+}
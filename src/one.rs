@@ -1,13 +1,14 @@
 use crate::{debug_unreachable, zip_exact, TraitImplMeta, TraitQuery, TraitQueryState};
-use bevy::ecs::change_detection::Mut;
-use bevy::ecs::component::{ComponentId, ComponentTicks, Tick};
+use bevy::ecs::change_detection::{Mut, Ref};
+use bevy::ecs::component::{ComponentId, Tick};
 use bevy::ecs::entity::Entity;
 use bevy::ecs::query::{QueryItem, ReadOnlyWorldQuery, WorldQuery};
-use bevy::ecs::storage::{ComponentSparseSet, SparseSets, TableRow};
+use bevy::ecs::storage::{Column, ComponentSparseSet, SparseSets, TableRow};
 use bevy::ecs::world::unsafe_world_cell::UnsafeWorldCell;
 use bevy::ecs::world::World;
 use bevy::ptr::{Ptr, ThinSlicePtr, UnsafeCellDeref};
 use std::cell::UnsafeCell;
+use std::marker::PhantomData;
 
 pub struct ReadTraitFetch<'w, Trait: ?Sized> {
     // While we have shared access to all sparse set components,
@@ -17,6 +18,8 @@ pub struct ReadTraitFetch<'w, Trait: ?Sized> {
     // After `Fetch::set_archetype` or `set_table` has been called,
     // this will carry the component data and metadata for the first trait impl found in the archetype.
     storage: ReadStorage<'w, Trait>,
+    last_run: Tick,
+    this_run: Tick,
 }
 
 enum ReadStorage<'w, Trait: ?Sized> {
@@ -27,7 +30,8 @@ enum ReadStorage<'w, Trait: ?Sized> {
         /// The fetch impl registers read access for all of these components,
         /// so there will be no runtime conflicts.
         column: Ptr<'w>,
-        ticks: Option<ThinSlicePtr<'w, UnsafeCell<ComponentTicks>>>,
+        added_ticks: ThinSlicePtr<'w, UnsafeCell<Tick>>,
+        changed_ticks: ThinSlicePtr<'w, UnsafeCell<Tick>>,
         meta: TraitImplMeta<Trait>,
     },
     SparseSet {
@@ -74,14 +78,59 @@ enum WriteStorage<'w, Trait: ?Sized> {
     },
 }
 
+/// The storage of a trait impl found by [`find_impl`].
+enum FoundImpl<'w> {
+    Table(&'w Column),
+    SparseSet(&'w ComponentSparseSet),
+}
+
+/// Finds the registered trait impl with the highest priority that is present in `archetype`.
+///
+/// # Safety
+/// `archetype` must contain at least one of the components in `state`,
+/// and `table` must be the table of `archetype`.
+#[inline]
+unsafe fn find_impl<'w, Trait: ?Sized + TraitQuery>(
+    state: &TraitQueryState<Trait>,
+    archetype: &bevy::ecs::archetype::Archetype,
+    table: &'w bevy::ecs::storage::Table,
+    sparse_sets: &'w SparseSets,
+) -> (FoundImpl<'w>, TraitImplMeta<Trait>) {
+    // The impls in the state are already sorted by priority.
+    for (&component, &meta) in zip_exact(&*state.components, &*state.meta) {
+        // A sparse set can exist for components that this archetype doesn't have.
+        if !archetype.contains(component) {
+            continue;
+        }
+        if let Some(column) = table.get_column(component) {
+            return (FoundImpl::Table(column), meta);
+        }
+        if let Some(sparse_set) = sparse_sets.get(component) {
+            return (FoundImpl::SparseSet(sparse_set), meta);
+        }
+    }
+    // At least one of the components must be present in the table/sparse set.
+    debug_unreachable()
+}
+
 /// [`WorldQuery`] adapter that fetches entities with exactly one component implementing a trait.
 pub struct One<T>(pub T);
 
-unsafe impl<'a, T: ?Sized + TraitQuery> ReadOnlyWorldQuery for One<&'a T> {}
+unsafe impl<T: ?Sized + TraitQuery> ReadOnlyWorldQuery for One<&T> {}
+
+/// [`WorldQuery`] adapter that fetches entities with exactly one component implementing a trait,
+/// iterating over tables instead of archetypes.
+///
+/// It matches the same entities as [`One`], but it requires every component
+/// implementing the trait to be stored in a table.
+/// Whether iterating over tables is faster depends on how the entities are laid out,
+/// so measure before switching (see the `all`, `one` and `fragmented` benches).
+/// Initializing this query will panic if any of them use sparse set storage.
+pub struct DenseOne<T>(pub T);
 
 /// SAFETY: We only access the components registered in `DynQueryState`.
 /// This same set of components is used to match archetypes, and used to register world access.
-unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for One<&'a Trait> {
+unsafe impl<Trait: ?Sized + TraitQuery> WorldQuery for One<&Trait> {
     type Item<'w> = &'w Trait;
     type Fetch<'w> = ReadTraitFetch<'w, Trait>;
     type ReadOnly = Self;
@@ -96,12 +145,14 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for One<&'a Trait> {
     unsafe fn init_fetch<'w>(
         world: UnsafeWorldCell<'w>,
         _state: &Self::State,
-        _last_run: Tick,
-        _this_run: Tick,
+        last_run: Tick,
+        this_run: Tick,
     ) -> ReadTraitFetch<'w, Trait> {
         ReadTraitFetch {
             storage: ReadStorage::Uninit,
             sparse_sets: &world.storages().sparse_sets,
+            last_run,
+            this_run,
         }
     }
 
@@ -112,11 +163,13 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for One<&'a Trait> {
                 ReadStorage::Uninit => ReadStorage::Uninit,
                 ReadStorage::Table {
                     column,
-                    ticks,
+                    added_ticks,
+                    changed_ticks,
                     meta,
                 } => ReadStorage::Table {
                     column,
-                    ticks,
+                    added_ticks,
+                    changed_ticks,
                     meta,
                 },
                 ReadStorage::SparseSet { components, meta } => {
@@ -124,6 +177,8 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for One<&'a Trait> {
                 }
             },
             sparse_sets: fetch.sparse_sets,
+            last_run: fetch.last_run,
+            this_run: fetch.this_run,
         }
     }
 
@@ -134,32 +189,19 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for One<&'a Trait> {
     unsafe fn set_archetype<'w>(
         fetch: &mut ReadTraitFetch<'w, Trait>,
         state: &Self::State,
-        _archetype: &'w bevy::ecs::archetype::Archetype,
+        archetype: &'w bevy::ecs::archetype::Archetype,
         table: &'w bevy::ecs::storage::Table,
     ) {
-        // Search for a registered trait impl that is present in the archetype.
-        // We check the table components first since it is faster to retrieve data of this type.
-        for (&component, &meta) in zip_exact(&*state.components, &*state.meta) {
-            if let Some(column) = table.get_column(component) {
-                fetch.storage = ReadStorage::Table {
-                    column: column.get_data_ptr(),
-                    ticks: None,
-                    meta,
-                };
-                return;
-            }
-        }
-        for (&component, &meta) in zip_exact(&*state.components, &*state.meta) {
-            if let Some(sparse_set) = fetch.sparse_sets.get(component) {
-                fetch.storage = ReadStorage::SparseSet {
-                    components: sparse_set,
-                    meta,
-                };
-                return;
-            }
-        }
-        // At least one of the components must be present in the table/sparse set.
-        debug_unreachable()
+        // `FirstImpl` shares this, and relies on it picking the highest priority impl.
+        fetch.storage = match find_impl(state, archetype, table, fetch.sparse_sets) {
+            (FoundImpl::Table(column), meta) => ReadStorage::Table {
+                column: column.get_data_ptr(),
+                added_ticks: column.get_added_ticks_slice().into(),
+                changed_ticks: column.get_changed_ticks_slice().into(),
+                meta,
+            },
+            (FoundImpl::SparseSet(components), meta) => ReadStorage::SparseSet { components, meta },
+        };
     }
 
     #[inline]
@@ -173,9 +215,11 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for One<&'a Trait> {
             if let Some(column) = table.get_column(component) {
                 fetch.storage = ReadStorage::Table {
                     column: column.get_data_ptr(),
-                    ticks: None,
+                    added_ticks: column.get_added_ticks_slice().into(),
+                    changed_ticks: column.get_changed_ticks_slice().into(),
                     meta,
-                }
+                };
+                return;
             }
         }
         // At least one of the components must be present in the table.
@@ -217,8 +261,9 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for One<&'a Trait> {
                 "&{} conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
                 std::any::type_name::<Trait>(),
             );
-            access.add_read(component);
+            access.access_mut().add_read(component);
         }
+        state.and_with_one(access);
     }
 
     #[inline]
@@ -309,32 +354,21 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for One<&'a mut Trait> {
     unsafe fn set_archetype<'w>(
         fetch: &mut WriteTraitFetch<'w, Trait>,
         state: &Self::State,
-        _archetype: &'w bevy::ecs::archetype::Archetype,
+        archetype: &'w bevy::ecs::archetype::Archetype,
         table: &'w bevy::ecs::storage::Table,
     ) {
-        // Search for a registered trait impl that is present in the archetype.
-        for (&component, &meta) in zip_exact(&*state.components, &*state.meta) {
-            if let Some(column) = table.get_column(component) {
-                fetch.storage = WriteStorage::Table {
-                    column: column.get_data_ptr(),
-                    added_ticks: column.get_added_ticks_slice().into(),
-                    changed_ticks: column.get_changed_ticks_slice().into(),
-                    meta,
-                };
-                return;
-            }
-        }
-        for (&component, &meta) in zip_exact(&*state.components, &*state.meta) {
-            if let Some(sparse_set) = fetch.sparse_sets.get(component) {
-                fetch.storage = WriteStorage::SparseSet {
-                    components: sparse_set,
-                    meta,
-                };
-                return;
+        // `FirstImpl` shares this, and relies on it picking the highest priority impl.
+        fetch.storage = match find_impl(state, archetype, table, fetch.sparse_sets) {
+            (FoundImpl::Table(column), meta) => WriteStorage::Table {
+                column: column.get_data_ptr(),
+                added_ticks: column.get_added_ticks_slice().into(),
+                changed_ticks: column.get_changed_ticks_slice().into(),
+                meta,
+            },
+            (FoundImpl::SparseSet(components), meta) => {
+                WriteStorage::SparseSet { components, meta }
             }
-        }
-        // At least one of the components must be present in the table/sparse set.
-        debug_unreachable()
+        };
     }
 
     #[inline]
@@ -428,8 +462,9 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for One<&'a mut Trait> {
                 "&mut {} conflicts with a previous access in this query. Mutable component access must be unique.",
                 std::any::type_name::<Trait>(),
             );
-            access.add_write(component);
+            access.access_mut().add_write(component);
         }
+        state.and_with_one(access);
     }
 
     #[inline]
@@ -457,3 +492,654 @@ unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for One<&'a mut Trait> {
         state.matches_component_set_one(set_contains_id)
     }
 }
+
+unsafe impl<T: ?Sized + TraitQuery> ReadOnlyWorldQuery for One<Ref<'_, T>> {}
+
+/// SAFETY: We only access the components registered in `DynQueryState`.
+/// This same set of components is used to match archetypes, and used to register world access.
+unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for One<Ref<'a, Trait>> {
+    type Item<'w> = Ref<'w, Trait>;
+    type Fetch<'w> = ReadTraitFetch<'w, Trait>;
+    type ReadOnly = Self;
+    type State = TraitQueryState<Trait>;
+
+    #[inline]
+    fn shrink<'wlong: 'wshort, 'wshort>(item: QueryItem<'wlong, Self>) -> QueryItem<'wshort, Self> {
+        item
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> ReadTraitFetch<'w, Trait> {
+        <One<&'a Trait> as WorldQuery>::init_fetch(world, state, last_run, this_run)
+    }
+
+    #[inline]
+    unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+        <One<&'a Trait> as WorldQuery>::clone_fetch(fetch)
+    }
+
+    const IS_DENSE: bool = <One<&'a Trait> as WorldQuery>::IS_DENSE;
+    const IS_ARCHETYPAL: bool = <One<&'a Trait> as WorldQuery>::IS_ARCHETYPAL;
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut ReadTraitFetch<'w, Trait>,
+        state: &Self::State,
+        archetype: &'w bevy::ecs::archetype::Archetype,
+        table: &'w bevy::ecs::storage::Table,
+    ) {
+        <One<&'a Trait> as WorldQuery>::set_archetype(fetch, state, archetype, table);
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(
+        fetch: &mut ReadTraitFetch<'w, Trait>,
+        state: &Self::State,
+        table: &'w bevy::ecs::storage::Table,
+    ) {
+        <One<&'a Trait> as WorldQuery>::set_table(fetch, state, table);
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Ref<'w, Trait> {
+        let table_row = table_row.index();
+        let dyn_ctor;
+        let (ptr, added, changed) = match fetch.storage {
+            // SAFETY: This function must have been called after `set_archetype`,
+            // so we know that `self.storage` has been initialized.
+            ReadStorage::Uninit => debug_unreachable(),
+            ReadStorage::Table {
+                column,
+                added_ticks,
+                changed_ticks,
+                meta,
+            } => {
+                dyn_ctor = meta.dyn_ctor;
+                (
+                    column.byte_add(table_row * meta.size_bytes),
+                    // SAFETY: We have shared access to the component, so by extension
+                    // we have shared access to the corresponding `ComponentTicks`.
+                    added_ticks.get(table_row).deref(),
+                    changed_ticks.get(table_row).deref(),
+                )
+            }
+            ReadStorage::SparseSet { components, meta } => {
+                dyn_ctor = meta.dyn_ctor;
+                let (ptr, ticks) = components
+                    .get_with_ticks(entity)
+                    .unwrap_or_else(|| debug_unreachable());
+                (ptr, ticks.added.deref(), ticks.changed.deref())
+            }
+        };
+
+        Ref::new(
+            dyn_ctor.cast(ptr),
+            added,
+            changed,
+            fetch.last_run,
+            fetch.this_run,
+        )
+    }
+
+    #[inline]
+    fn update_component_access(
+        state: &Self::State,
+        access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+    ) {
+        <One<&'a Trait> as WorldQuery>::update_component_access(state, access);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &bevy::ecs::archetype::Archetype,
+        access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+    ) {
+        <One<&'a Trait> as WorldQuery>::update_archetype_component_access(state, archetype, access);
+    }
+
+    #[inline]
+    fn init_state(world: &mut World) -> Self::State {
+        TraitQueryState::init(world)
+    }
+
+    #[inline]
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        state.matches_component_set_one(set_contains_id)
+    }
+}
+
+/// [`WorldQuery`] adapter that fetches entities with at least one component implementing a trait.
+///
+/// If an entity has several such components, only the one with the highest priority is returned.
+/// See [`RegisterExt::register_component_as_with_priority`](crate::RegisterExt::register_component_as_with_priority).
+pub struct FirstImpl<T>(pub T);
+
+unsafe impl<T: ?Sized + TraitQuery> ReadOnlyWorldQuery for FirstImpl<&T> {}
+
+/// SAFETY: We only access the components registered in `DynQueryState`.
+/// This same set of components is used to match archetypes, and used to register world access.
+unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for FirstImpl<&'a Trait> {
+    type Item<'w> = &'w Trait;
+    type Fetch<'w> = ReadTraitFetch<'w, Trait>;
+    type ReadOnly = Self;
+    type State = TraitQueryState<Trait>;
+
+    #[inline]
+    fn shrink<'wlong: 'wshort, 'wshort>(item: QueryItem<'wlong, Self>) -> QueryItem<'wshort, Self> {
+        item
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> ReadTraitFetch<'w, Trait> {
+        <One<&'a Trait> as WorldQuery>::init_fetch(world, state, last_run, this_run)
+    }
+
+    #[inline]
+    unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+        <One<&'a Trait> as WorldQuery>::clone_fetch(fetch)
+    }
+
+    const IS_DENSE: bool = false;
+    const IS_ARCHETYPAL: bool = false;
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut ReadTraitFetch<'w, Trait>,
+        state: &Self::State,
+        archetype: &'w bevy::ecs::archetype::Archetype,
+        table: &'w bevy::ecs::storage::Table,
+    ) {
+        // This picks the highest priority impl that is present in the archetype.
+        <One<&'a Trait> as WorldQuery>::set_archetype(fetch, state, archetype, table);
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(
+        fetch: &mut ReadTraitFetch<'w, Trait>,
+        state: &Self::State,
+        table: &'w bevy::ecs::storage::Table,
+    ) {
+        // The impls in the state are sorted by priority, so this picks the highest priority impl.
+        <One<&'a Trait> as WorldQuery>::set_table(fetch, state, table);
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Self::Item<'w> {
+        <One<&'a Trait> as WorldQuery>::fetch(fetch, entity, table_row)
+    }
+
+    #[inline]
+    fn update_component_access(
+        state: &Self::State,
+        access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+    ) {
+        for &component in &*state.components {
+            assert!(
+                !access.access().has_write(component),
+                "&{} conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+                std::any::type_name::<Trait>(),
+            );
+            access.access_mut().add_read(component);
+        }
+        state.and_with_any(access);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &bevy::ecs::archetype::Archetype,
+        access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+    ) {
+        <One<&'a Trait> as WorldQuery>::update_archetype_component_access(state, archetype, access);
+    }
+
+    #[inline]
+    fn init_state(world: &mut World) -> Self::State {
+        TraitQueryState::init(world)
+    }
+
+    #[inline]
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        state.matches_component_set_any(set_contains_id)
+    }
+}
+
+/// SAFETY: We only access the components registered in `DynQueryState`.
+/// This same set of components is used to match archetypes, and used to register world access.
+unsafe impl<'a, Trait: ?Sized + TraitQuery> WorldQuery for FirstImpl<&'a mut Trait> {
+    type Item<'w> = Mut<'w, Trait>;
+    type Fetch<'w> = WriteTraitFetch<'w, Trait>;
+    type ReadOnly = FirstImpl<&'a Trait>;
+    type State = TraitQueryState<Trait>;
+
+    #[inline]
+    fn shrink<'wlong: 'wshort, 'wshort>(item: QueryItem<'wlong, Self>) -> QueryItem<'wshort, Self> {
+        item
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> WriteTraitFetch<'w, Trait> {
+        <One<&'a mut Trait> as WorldQuery>::init_fetch(world, state, last_run, this_run)
+    }
+
+    #[inline]
+    unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+        <One<&'a mut Trait> as WorldQuery>::clone_fetch(fetch)
+    }
+
+    const IS_DENSE: bool = false;
+    const IS_ARCHETYPAL: bool = false;
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut WriteTraitFetch<'w, Trait>,
+        state: &Self::State,
+        archetype: &'w bevy::ecs::archetype::Archetype,
+        table: &'w bevy::ecs::storage::Table,
+    ) {
+        // This picks the highest priority impl that is present in the archetype.
+        <One<&'a mut Trait> as WorldQuery>::set_archetype(fetch, state, archetype, table);
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(
+        fetch: &mut WriteTraitFetch<'w, Trait>,
+        state: &Self::State,
+        table: &'w bevy::ecs::storage::Table,
+    ) {
+        // The impls in the state are sorted by priority, so this picks the highest priority impl.
+        <One<&'a mut Trait> as WorldQuery>::set_table(fetch, state, table);
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Mut<'w, Trait> {
+        <One<&'a mut Trait> as WorldQuery>::fetch(fetch, entity, table_row)
+    }
+
+    #[inline]
+    fn update_component_access(
+        state: &Self::State,
+        access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+    ) {
+        for &component in &*state.components {
+            assert!(
+                !access.access().has_write(component),
+                "&mut {} conflicts with a previous access in this query. Mutable component access must be unique.",
+                std::any::type_name::<Trait>(),
+            );
+            access.access_mut().add_write(component);
+        }
+        state.and_with_any(access);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &bevy::ecs::archetype::Archetype,
+        access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+    ) {
+        <One<&'a mut Trait> as WorldQuery>::update_archetype_component_access(
+            state, archetype, access,
+        );
+    }
+
+    #[inline]
+    fn init_state(world: &mut World) -> Self::State {
+        TraitQueryState::init(world)
+    }
+
+    #[inline]
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        state.matches_component_set_any(set_contains_id)
+    }
+}
+
+#[doc(hidden)]
+pub struct OneTickFilterFetch<'w> {
+    // While we have shared access to all sparse set components,
+    // in practice we will only read the ticks of the components specified in the `FetchState`.
+    sparse_sets: &'w SparseSets,
+    // After `Fetch::set_archetype` or `set_table` has been called,
+    // this will carry the ticks for the trait impl found in the archetype.
+    storage: TickFilterStorage<'w>,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+#[derive(Clone, Copy)]
+enum TickFilterStorage<'w> {
+    Uninit,
+    Table {
+        /// This points to either the added or changed ticks of one of the component table columns,
+        /// corresponding to one of the `ComponentId`s in the fetch state.
+        /// The fetch impl registers read access for all of these components,
+        /// so there will be no runtime conflicts.
+        ticks: ThinSlicePtr<'w, UnsafeCell<Tick>>,
+    },
+    SparseSet {
+        /// This gives us access to the ticks of one of the components implementing the trait.
+        /// The fetch impl registers read access for all components implementing the trait,
+        /// so there will not be any runtime conflicts.
+        components: &'w ComponentSparseSet,
+    },
+}
+
+macro_rules! impl_one_tick_filter {
+    (
+        $(#[$meta:meta])*
+        $name:ident,
+        $get_slice:expr,
+        $get_sparse_set:expr
+    ) => {
+        $(#[$meta])*
+        pub struct $name<Trait: ?Sized>(PhantomData<Trait>);
+
+        unsafe impl<Trait: ?Sized + TraitQuery> ReadOnlyWorldQuery for $name<Trait> {}
+
+        /// SAFETY: We only access the ticks of the components registered in `DynQueryState`.
+        /// This same set of components is used to match archetypes, and used to register world access.
+        unsafe impl<Trait: ?Sized + TraitQuery> WorldQuery for $name<Trait> {
+            type Item<'w> = bool;
+            type Fetch<'w> = OneTickFilterFetch<'w>;
+            type ReadOnly = Self;
+            type State = TraitQueryState<Trait>;
+
+            #[inline]
+            fn shrink<'wlong: 'wshort, 'wshort>(
+                item: QueryItem<'wlong, Self>,
+            ) -> QueryItem<'wshort, Self> {
+                item
+            }
+
+            #[inline]
+            unsafe fn init_fetch<'w>(
+                world: UnsafeWorldCell<'w>,
+                _state: &Self::State,
+                last_run: Tick,
+                this_run: Tick,
+            ) -> OneTickFilterFetch<'w> {
+                OneTickFilterFetch {
+                    sparse_sets: &world.storages().sparse_sets,
+                    storage: TickFilterStorage::Uninit,
+                    last_run,
+                    this_run,
+                }
+            }
+
+            #[inline]
+            unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+                OneTickFilterFetch {
+                    sparse_sets: fetch.sparse_sets,
+                    storage: fetch.storage,
+                    last_run: fetch.last_run,
+                    this_run: fetch.this_run,
+                }
+            }
+
+            const IS_DENSE: bool = false;
+            const IS_ARCHETYPAL: bool = false;
+
+            #[inline]
+            unsafe fn set_archetype<'w>(
+                fetch: &mut OneTickFilterFetch<'w>,
+                state: &Self::State,
+                archetype: &'w bevy::ecs::archetype::Archetype,
+                table: &'w bevy::ecs::storage::Table,
+            ) {
+                // Search for a registered trait impl that is present in the archetype.
+                // We check the table components first since it is faster to retrieve data of this type.
+                for &component in &*state.components {
+                    if let Some(column) = table.get_column(component) {
+                        fetch.storage = TickFilterStorage::Table {
+                            ticks: $get_slice(column).into(),
+                        };
+                        return;
+                    }
+                }
+                for &component in &*state.components {
+                    if archetype.contains(component) {
+                        fetch.storage = TickFilterStorage::SparseSet {
+                            components: fetch
+                                .sparse_sets
+                                .get(component)
+                                .unwrap_or_else(|| debug_unreachable()),
+                        };
+                        return;
+                    }
+                }
+                // At least one of the components must be present in the table/sparse set.
+                debug_unreachable()
+            }
+
+            #[inline]
+            unsafe fn set_table<'w>(
+                fetch: &mut OneTickFilterFetch<'w>,
+                state: &Self::State,
+                table: &'w bevy::ecs::storage::Table,
+            ) {
+                // Search for a registered trait impl that is present in the table.
+                for &component in &*state.components {
+                    if let Some(column) = table.get_column(component) {
+                        fetch.storage = TickFilterStorage::Table {
+                            ticks: $get_slice(column).into(),
+                        };
+                        return;
+                    }
+                }
+                // At least one of the components must be present in the table.
+                debug_unreachable()
+            }
+
+            #[inline]
+            unsafe fn fetch<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                entity: Entity,
+                table_row: TableRow,
+            ) -> Self::Item<'w> {
+                let ticks = match fetch.storage {
+                    // SAFETY: This function must have been called after `set_archetype`,
+                    // so we know that `self.storage` has been initialized.
+                    TickFilterStorage::Uninit => debug_unreachable(),
+                    TickFilterStorage::Table { ticks } => ticks.get(table_row.index()),
+                    TickFilterStorage::SparseSet { components } => {
+                        $get_sparse_set(components, entity).unwrap_or_else(|| debug_unreachable())
+                    }
+                };
+                ticks.read().is_newer_than(fetch.last_run, fetch.this_run)
+            }
+
+            #[inline]
+            unsafe fn filter_fetch(
+                fetch: &mut Self::Fetch<'_>,
+                entity: Entity,
+                table_row: TableRow,
+            ) -> bool {
+                Self::fetch(fetch, entity, table_row)
+            }
+
+            #[inline]
+            fn update_component_access(
+                state: &Self::State,
+                access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+            ) {
+                for &component in &*state.components {
+                    assert!(
+                        !access.access().has_write(component),
+                        "{}<{}> conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+                        stringify!($name),
+                        std::any::type_name::<Trait>(),
+                    );
+                    access.access_mut().add_read(component);
+                }
+                state.and_with_one(access);
+            }
+
+            #[inline]
+            fn update_archetype_component_access(
+                state: &Self::State,
+                archetype: &bevy::ecs::archetype::Archetype,
+                access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+            ) {
+                for &component in &*state.components {
+                    if let Some(archetype_component_id) =
+                        archetype.get_archetype_component_id(component)
+                    {
+                        access.add_read(archetype_component_id);
+                    }
+                }
+            }
+
+            #[inline]
+            fn init_state(world: &mut World) -> Self::State {
+                TraitQueryState::init(world)
+            }
+
+            #[inline]
+            fn matches_component_set(
+                state: &Self::State,
+                set_contains_id: &impl Fn(ComponentId) -> bool,
+            ) -> bool {
+                state.matches_component_set_one(set_contains_id)
+            }
+        }
+    };
+}
+
+impl_one_tick_filter!(
+    /// [`WorldQuery`] filter for entities with exactly one component implementing a trait,
+    /// whose value has changed since the last time the system ran.
+    OneChanged,
+    Column::get_changed_ticks_slice,
+    ComponentSparseSet::get_changed_ticks
+);
+
+impl_one_tick_filter!(
+    /// [`WorldQuery`] filter for entities with exactly one component implementing a trait,
+    /// which was added since the last time the system ran.
+    OneAdded,
+    Column::get_added_ticks_slice,
+    ComponentSparseSet::get_added_ticks
+);
+
+/// [`WorldQuery`] filter for entities with exactly one component implementing a trait.
+///
+/// Unlike `One<&dyn Trait>`, this does not register access to any components,
+/// so it will never conflict with other queries.
+pub struct WithOneTrait<Trait: ?Sized>(PhantomData<Trait>);
+
+unsafe impl<Trait: ?Sized + TraitQuery> ReadOnlyWorldQuery for WithOneTrait<Trait> {}
+
+/// SAFETY: This does not access any component data.
+unsafe impl<Trait: ?Sized + TraitQuery> WorldQuery for WithOneTrait<Trait> {
+    type Item<'w> = ();
+    type Fetch<'w> = ();
+    type ReadOnly = Self;
+    type State = TraitQueryState<Trait>;
+
+    #[inline]
+    fn shrink<'wlong: 'wshort, 'wshort>(_: QueryItem<'wlong, Self>) -> QueryItem<'wshort, Self> {}
+
+    #[inline]
+    unsafe fn init_fetch(
+        _world: UnsafeWorldCell<'_>,
+        _state: &Self::State,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn clone_fetch<'w>(_fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {}
+
+    const IS_DENSE: bool = false;
+    const IS_ARCHETYPAL: bool = true;
+
+    #[inline]
+    unsafe fn set_archetype(
+        _fetch: &mut (),
+        _state: &Self::State,
+        _archetype: &bevy::ecs::archetype::Archetype,
+        _table: &bevy::ecs::storage::Table,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn set_table(_fetch: &mut (), _state: &Self::State, _table: &bevy::ecs::storage::Table) {
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(
+        _fetch: &mut Self::Fetch<'w>,
+        _entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+    }
+
+    #[inline]
+    fn update_component_access(
+        state: &Self::State,
+        access: &mut bevy::ecs::query::FilteredAccess<ComponentId>,
+    ) {
+        state.and_with_one(access);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        _state: &Self::State,
+        _archetype: &bevy::ecs::archetype::Archetype,
+        _access: &mut bevy::ecs::query::Access<bevy::ecs::archetype::ArchetypeComponentId>,
+    ) {
+    }
+
+    #[inline]
+    fn init_state(world: &mut World) -> Self::State {
+        TraitQueryState::init(world)
+    }
+
+    #[inline]
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        state.matches_component_set_one(set_contains_id)
+    }
+}
+
+impl_dense_query!(DenseOne, One);
@@ -0,0 +1,174 @@
+//! Integration with `bevy_reflect`, for registering trait impls from the type registry.
+
+use crate::{DynCtor, RegisterExt, TraitImplMeta, TraitImplRegistry, TraitQuery, TraitQueryMarker};
+use bevy::ecs::component::Component;
+use bevy::ecs::reflect::{AppTypeRegistry, ReflectComponent};
+use bevy::ecs::world::{EntityMut, World};
+use bevy::ptr::{Ptr, PtrMut};
+use bevy::reflect::{FromType, Reflect, ReflectFromPtr};
+use bevy::utils::default;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+/// Type data that allows a component to be registered with trait queries for `Trait`
+/// using [`RegisterExt::register_traits_from_type_registry`].
+///
+/// Traits marked with `#[queryable(reflect)]` get a type alias for this named after the trait,
+/// so that it can be used in `#[reflect(...)]` attributes:
+///
+/// ```ignore
+/// # use bevy::prelude::*;
+/// # use bevy_trait_query::*;
+/// #
+/// #[bevy_trait_query::queryable(reflect)]
+/// pub trait Tooltip {
+///     fn tooltip(&self) -> &str;
+/// }
+///
+/// #[derive(Component, Reflect)]
+/// #[reflect(Tooltip)]
+/// struct Button;
+///
+/// impl Tooltip for Button {
+///     fn tooltip(&self) -> &str {
+///         "Click me!"
+///     }
+/// }
+///
+/// App::new()
+///     .register_type::<Button>()
+///     .register_traits_from_type_registry::<dyn Tooltip>();
+/// ```
+pub struct ReflectTraitQuery<Trait: ?Sized> {
+    register: fn(&mut World),
+    marker: PhantomData<fn() -> *const Trait>,
+}
+
+impl<Trait: ?Sized> Clone for ReflectTraitQuery<Trait> {
+    fn clone(&self) -> Self {
+        Self {
+            register: self.register,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Trait: ?Sized + TraitQuery> ReflectTraitQuery<Trait> {
+    /// Registers the component that this type data belongs to with trait queries for `Trait`.
+    ///
+    /// See [`RegisterExt::register_component_as`].
+    pub fn register(&self, world: &mut World) {
+        (self.register)(world);
+    }
+}
+
+impl<Trait: ?Sized + TraitQuery, C: Component> FromType<C> for ReflectTraitQuery<Trait>
+where
+    (C,): TraitQueryMarker<Trait, Covered = C>,
+{
+    fn from_type() -> Self {
+        Self {
+            register: |world| {
+                world.register_component_as::<Trait, C>();
+            },
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Every reflected component can be queried as `dyn Reflect`,
+/// once it has been registered with [`RegisterExt::register_reflect_components`].
+///
+/// Since `Reflect` is not defined in this crate, `&dyn Reflect` can't be used as a query by itself.
+/// Use [`All<&dyn Reflect>`](crate::All) or [`One<&dyn Reflect>`](crate::One) instead.
+impl TraitQuery for dyn Reflect {}
+
+/// The type data needed to access a reflected component without knowing its type.
+struct ReflectImpl {
+    from_ptr: ReflectFromPtr,
+    component: ReflectComponent,
+}
+
+/// Registers every component in the [`AppTypeRegistry`] with trait queries for `dyn Reflect`.
+pub(crate) fn register_reflect_components(world: &mut World) -> &mut World {
+    let Some(type_registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+        return world;
+    };
+    let type_registry = type_registry.read();
+    // The registry is a hash map, so sort the components to register them in a consistent order.
+    let mut registrations: Vec<_> = type_registry.iter().collect();
+    registrations.sort_by_key(|registration| registration.type_name());
+    for registration in registrations {
+        let (Some(from_ptr), Some(component)) = (
+            registration.data::<ReflectFromPtr>(),
+            registration.data::<ReflectComponent>(),
+        ) else {
+            continue;
+        };
+        // Without the concrete type, components can only be registered once the world knows about them.
+        let Some(info) = world
+            .components()
+            .get_id(registration.type_id())
+            .and_then(|id| world.components().get_info(id))
+        else {
+            continue;
+        };
+        let (component_id, size_bytes, storage_type) =
+            (info.id(), info.layout().size(), info.storage_type());
+
+        let registry = world
+            .get_resource_or_insert_with::<TraitImplRegistry<dyn Reflect>>(default)
+            .into_inner();
+        if registry.lists.components.contains(&component_id) {
+            continue;
+        }
+        let data = Arc::new(ReflectImpl {
+            from_ptr: from_ptr.clone(),
+            component: component.clone(),
+        });
+        let meta = TraitImplMeta {
+            priority: 0,
+            // Assigned by the registry.
+            order: 0,
+            size_bytes,
+            storage_type,
+            type_id: registration.type_id(),
+            type_name: registration.type_name(),
+            dyn_ctor: DynCtor {
+                cast: cast_reflect,
+                cast_mut: cast_reflect_mut,
+                data: Arc::as_ptr(&data).cast(),
+            },
+            remove: remove_reflect,
+            clone_into: None,
+        };
+        if let Err(err) = registry.register(component_id, meta) {
+            panic!("{err}");
+        }
+        registry.keep_type_data(data);
+    }
+    world
+}
+
+/// # Safety
+/// `data` must point to the [`ReflectImpl`] for the type of the component that `ptr` points to.
+unsafe fn cast_reflect(ptr: *mut u8, data: *const ()) -> *mut dyn Reflect {
+    let data = &*data.cast::<ReflectImpl>();
+    let ptr = Ptr::new(NonNull::new_unchecked(ptr));
+    data.from_ptr.as_reflect_ptr(ptr) as *const dyn Reflect as *mut dyn Reflect
+}
+
+/// # Safety
+/// Same as [`cast_reflect`], and the component must not be aliased.
+unsafe fn cast_reflect_mut(ptr: *mut u8, data: *const ()) -> *mut dyn Reflect {
+    let data = &*data.cast::<ReflectImpl>();
+    let ptr = PtrMut::new(NonNull::new_unchecked(ptr));
+    data.from_ptr.as_reflect_ptr_mut(ptr)
+}
+
+fn remove_reflect(entity: &mut EntityMut, data: *const ()) {
+    // SAFETY: `data` is the `ReflectImpl` that the registry kept when the component was registered.
+    let data = unsafe { &*data.cast::<ReflectImpl>() };
+    data.component.remove(entity);
+}
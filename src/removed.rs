@@ -0,0 +1,104 @@
+use crate::{TraitImplRegistry, TraitQuery};
+use bevy::ecs::component::ComponentId;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::event::ManualEventReader;
+use bevy::ecs::removal_detection::{RemovedComponentEntity, RemovedComponentEvents};
+use bevy::ecs::system::{Local, Res, SystemParam};
+
+/// A [`SystemParam`] that yields the entities that had a component implementing `Trait` removed,
+/// along with the ID of the component that was removed.
+///
+/// This works like bevy's [`RemovedComponents`](bevy::ecs::removal_detection::RemovedComponents),
+/// but it reads the removal events of every component registered with `Trait`.
+/// Each system using this parameter keeps track of which events it has already read.
+///
+/// ```ignore
+/// # use bevy::prelude::*;
+/// # use bevy_trait_query::*;
+/// #
+/// # #[bevy_trait_query::queryable]
+/// # pub trait Tooltip {
+/// #     fn tooltip(&self) -> &str;
+/// # }
+/// #
+/// fn lost_tooltips(mut removed: RemovedTraitComponents<dyn Tooltip>) {
+///     for (entity, component) in removed.iter() {
+///         println!("{entity:?} lost the tooltip component {component:?}");
+///     }
+/// }
+/// # bevy::ecs::system::assert_is_system(lost_tooltips);
+/// ```
+#[derive(SystemParam)]
+pub struct RemovedTraitComponents<'w, 's, Trait: ?Sized + TraitQuery> {
+    registry: Option<Res<'w, TraitImplRegistry<Trait>>>,
+    readers: Local<'s, RemovedTraitReaders>,
+    event_sets: &'w RemovedComponentEvents,
+}
+
+/// The event readers for each component that a [`RemovedTraitComponents`] has seen.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct RemovedTraitReaders(Vec<(ComponentId, ManualEventReader<RemovedComponentEntity>)>);
+
+impl<'w, 's, Trait: ?Sized + TraitQuery> RemovedTraitComponents<'w, 's, Trait> {
+    /// Iterates over the entities that had a component implementing `Trait` removed
+    /// since the last time this was called, along with the component that was removed.
+    ///
+    /// The removals are grouped by component, rather than being in the order they happened.
+    pub fn iter(&mut self) -> impl Iterator<Item = (Entity, ComponentId)> + '_ {
+        self.add_readers();
+        let event_sets = self.event_sets;
+        self.readers
+            .0
+            .iter_mut()
+            .filter_map(move |(component, reader)| {
+                let events = event_sets.get(*component)?;
+                Some(
+                    reader
+                        .iter(events)
+                        .map(move |entity| (entity.clone().into(), *component)),
+                )
+            })
+            .flatten()
+    }
+
+    /// Returns the number of removals that have not been read yet.
+    pub fn len(&self) -> usize {
+        let event_sets = self.event_sets;
+        let read: usize = (self.readers.0.iter())
+            .filter_map(|(component, reader)| Some(reader.len(event_sets.get(*component)?)))
+            .sum();
+        // Components without a reader yet would get a fresh one, which hasn't read anything.
+        let unread: usize = (self.registry.iter())
+            .flat_map(|registry| &registry.lists.components)
+            .filter(|&&component| !self.readers.0.iter().any(|&(c, _)| c == component))
+            .filter_map(|&component| {
+                let events = event_sets.get(component)?;
+                Some(ManualEventReader::<RemovedComponentEntity>::default().len(events))
+            })
+            .sum();
+        read + unread
+    }
+
+    /// Returns `true` if there are no removals that have not been read yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Consumes all of the removals that have not been read yet.
+    pub fn clear(&mut self) {
+        self.iter().for_each(drop);
+    }
+
+    /// Creates readers for any components that have been registered since the last call.
+    fn add_readers(&mut self) {
+        let Some(registry) = &self.registry else {
+            return;
+        };
+        for &component in &registry.lists.components {
+            if !self.readers.0.iter().any(|&(c, _)| c == component) {
+                self.readers.0.push((component, Default::default()));
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use bevy::ecs::query::WorldQuery;
 use std::fmt::{Debug, Display};
 
 #[derive(Resource, Default)]
@@ -187,7 +188,7 @@ pub trait Messages {
     fn read(&self) -> &[String];
 }
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct RecA(Vec<String>);
 
 #[derive(Component)]
@@ -326,3 +327,1502 @@ fn associated_type_system<T: Display + 'static>(_q: Query<&dyn AssociatedTrait<T
     // Assert that this current function is a system.
     let _x = IntoSystem::into_system(associated_type_system::<T>);
 }
+
+#[test]
+fn changed_all() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Person, Human>()
+        .register_component_as::<dyn Person, Dolphin>();
+
+    world.spawn(Human("Henry".to_owned(), 22));
+    world.spawn((Human("Eliza".to_owned(), 31), Fem, Dolphin(6)));
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems((print_changed, age_up_dolphins).chain());
+
+    fn print_changed(people: Query<ChangedAll<&dyn Person>>, mut output: ResMut<Output>) {
+        output.0.push("Changed:".to_string());
+        for all in &people {
+            for person in all {
+                output
+                    .0
+                    .push(format!("{}: {}", person.name(), person.age()));
+            }
+        }
+    }
+
+    // Only ages up dolphins that have changed since this system last ran,
+    // so this should only happen on the first frame.
+    fn age_up_dolphins(mut q: Query<ChangedAll<&mut dyn Person>, With<Fem>>) {
+        for all in &mut q {
+            for mut p in all {
+                if p.name() == "Reginald" {
+                    let age = p.age();
+                    p.set_age(age + 1);
+                }
+            }
+        }
+    }
+
+    schedule.run(&mut world);
+    schedule.run(&mut world);
+    schedule.run(&mut world);
+
+    assert_eq!(
+        world.resource::<Output>().0,
+        &[
+            "Changed:",
+            "Henry: 22",
+            "Eliza: 31",
+            "Reginald: 6",
+            "Changed:",
+            "Reginald: 7",
+            "Changed:",
+        ]
+    );
+}
+
+#[test]
+fn added_all() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    let entity = world.spawn(RecA(vec![])).id();
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(print_added);
+
+    fn print_added(q: Query<AddedAll<&dyn Messages>>, mut output: ResMut<Output>) {
+        output.0.push("Added:".to_owned());
+        for (i, all) in q.iter().enumerate() {
+            for msgs in all {
+                output.0.push(format!("{i}: {:?}", msgs.read()));
+            }
+        }
+    }
+
+    schedule.run(&mut world);
+    world
+        .entity_mut(entity)
+        .insert(RecB(vec!["Sparse".to_owned()]));
+    schedule.run(&mut world);
+    schedule.run(&mut world);
+
+    assert_eq!(
+        world.resource::<Output>().0,
+        &["Added:", "0: []", "Added:", r#"0: ["Sparse"]"#, "Added:"]
+    );
+}
+
+#[test]
+fn added_all_get() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    let entity = world.spawn(RecA(vec![])).id();
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(print_added);
+
+    fn print_added(mut q: Query<AddedAll<&mut dyn Messages>>, mut output: ResMut<Output>) {
+        for mut all in &mut q {
+            let a = all.get::<RecA>().is_some();
+            let b = all.get_mut::<RecB>().is_some();
+            output.0.push(format!("RecA: {a}, RecB: {b}"));
+        }
+    }
+
+    schedule.run(&mut world);
+    world.entity_mut(entity).insert(RecB(vec![]));
+    // `get` and `get_mut` skip the components that weren't added since the last run.
+    schedule.run(&mut world);
+
+    assert_eq!(
+        world.resource::<Output>().0,
+        ["RecA: true, RecB: false", "RecA: false, RecB: true"]
+    );
+}
+
+#[test]
+fn tick_filters() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    let a = world.spawn(RecA(vec![])).id();
+    let b = world.spawn(RecB(vec![])).id();
+    let ab = world.spawn((RecA(vec![]), RecB(vec![]))).id();
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(print_filtered);
+
+    fn print_filtered(
+        one_added: Query<Entity, OneAdded<dyn Messages>>,
+        one_changed: Query<Entity, OneChanged<dyn Messages>>,
+        any_added: Query<Entity, AnyAdded<dyn Messages>>,
+        any_changed: Query<Entity, AnyChanged<dyn Messages>>,
+        mut output: ResMut<Output>,
+    ) {
+        let mut push = |name: &str, entities: Vec<Entity>| {
+            let entities: Vec<_> = entities.iter().map(|e| e.index()).collect();
+            output.0.push(format!("{name}: {entities:?}"));
+        };
+        push("OneAdded", one_added.iter().collect());
+        push("OneChanged", one_changed.iter().collect());
+        push("AnyAdded", any_added.iter().collect());
+        push("AnyChanged", any_changed.iter().collect());
+    }
+
+    schedule.run(&mut world);
+    world.get_mut::<RecB>(b).unwrap().send(&"Hi");
+    world.get_mut::<RecB>(ab).unwrap().send(&"Hi");
+    schedule.run(&mut world);
+    world.get_mut::<RecA>(a).unwrap().send(&"Hi");
+    schedule.run(&mut world);
+
+    assert_eq!(
+        world.resource::<Output>().0,
+        &[
+            "OneAdded: [0, 1]",
+            "OneChanged: [0, 1]",
+            "AnyAdded: [0, 1, 2]",
+            "AnyChanged: [0, 1, 2]",
+            "OneAdded: []",
+            "OneChanged: [1]",
+            "AnyAdded: []",
+            "AnyChanged: [1, 2]",
+            "OneAdded: []",
+            "OneChanged: [0]",
+            "AnyAdded: []",
+            "AnyChanged: [0]",
+        ]
+    );
+}
+
+#[test]
+fn ref_change_detection() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    let a = world.spawn(RecA(vec![])).id();
+    let ab = world.spawn((RecA(vec![]), RecB(vec![]))).id();
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems((print_one_ticks, print_all_ticks).chain());
+
+    fn print_one_ticks(q: Query<One<Ref<dyn Messages>>>, mut output: ResMut<Output>) {
+        for msgs in &q {
+            output.0.push(format!(
+                "One: {:?}, added: {}, changed: {}",
+                msgs.read(),
+                msgs.is_added(),
+                msgs.is_changed()
+            ));
+        }
+    }
+
+    fn print_all_ticks(q: Query<&dyn Messages>, mut output: ResMut<Output>) {
+        for all in &q {
+            for msgs in all.iter_ref() {
+                output.0.push(format!(
+                    "All: {:?}, added: {}, changed: {}",
+                    msgs.read(),
+                    msgs.is_added(),
+                    msgs.is_changed()
+                ));
+            }
+        }
+    }
+
+    schedule.run(&mut world);
+    world.get_mut::<RecA>(a).unwrap().send(&"a");
+    world.get_mut::<RecB>(ab).unwrap().send(&"b");
+    world.resource_mut::<Output>().0.push("".to_owned());
+    schedule.run(&mut world);
+    world.resource_mut::<Output>().0.push("".to_owned());
+    schedule.run(&mut world);
+
+    assert_eq!(
+        world.resource::<Output>().0,
+        &[
+            "One: [], added: true, changed: true",
+            "All: [], added: true, changed: true",
+            "All: [], added: true, changed: true",
+            "All: [], added: true, changed: true",
+            "",
+            r#"One: ["RecA: a"], added: false, changed: true"#,
+            r#"All: ["RecA: a"], added: false, changed: true"#,
+            "All: [], added: false, changed: false",
+            r#"All: ["RecB: b"], added: false, changed: true"#,
+            "",
+            r#"One: ["RecA: a"], added: false, changed: false"#,
+            r#"All: ["RecA: a"], added: false, changed: false"#,
+            "All: [], added: false, changed: false",
+            r#"All: ["RecB: b"], added: false, changed: false"#,
+        ]
+    );
+}
+
+#[test]
+fn archetypal_filters() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Person, Human>()
+        .register_component_as::<dyn Person, Dolphin>();
+
+    world.spawn(Fem);
+    world.spawn((Human("Henry".to_owned(), 22), Fem));
+    world.spawn((Human("Eliza".to_owned(), 31), Fem, Dolphin(6)));
+    world.spawn(Dolphin(27));
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(count_filtered);
+
+    // The first and third queries would conflict if the filters registered any component access,
+    // or if the filters weren't known to be disjoint.
+    #[allow(clippy::type_complexity)]
+    fn count_filtered(
+        with: Query<&mut Fem, WithTrait<dyn Person>>,
+        with_one: Query<Entity, (WithOneTrait<dyn Person>, Without<Dolphin>)>,
+        without: Query<&Fem, WithoutTrait<dyn Person>>,
+        either: Query<Entity, Or<(WithoutTrait<dyn Person>, With<Dolphin>)>>,
+        mut output: ResMut<Output>,
+    ) {
+        output.0.push(format!("With: {}", with.iter().count()));
+        output
+            .0
+            .push(format!("WithOne: {}", with_one.iter().count()));
+        output
+            .0
+            .push(format!("Without: {}", without.iter().count()));
+        output.0.push(format!("Either: {}", either.iter().count()));
+    }
+
+    schedule.run(&mut world);
+
+    assert_eq!(
+        world.resource::<Output>().0,
+        &["With: 2", "WithOne: 1", "Without: 1", "Either: 3"]
+    );
+}
+
+#[test]
+fn optional_traits() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Person, Human>()
+        .register_component_as::<dyn Person, Dolphin>();
+
+    world.spawn(Fem);
+    world.spawn((Human("Henry".to_owned(), 22), Fem));
+    world.spawn((Human("Eliza".to_owned(), 31), Fem, Dolphin(6)));
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(print_optional);
+
+    fn print_optional(
+        maybe_all: Query<MaybeAll<&dyn Person>, With<Fem>>,
+        option_one: Query<Option<One<&dyn Person>>, With<Fem>>,
+        option_all: Query<Option<&dyn Person>, With<Fem>>,
+        mut output: ResMut<Output>,
+    ) {
+        for all in &maybe_all {
+            let names: Vec<_> = all.iter().map(|p| p.name()).collect();
+            output.0.push(format!("MaybeAll: {names:?}"));
+        }
+        for one in &option_one {
+            output
+                .0
+                .push(format!("Option<One>: {:?}", one.map(|p| p.name())));
+        }
+        for all in &option_all {
+            let count = all.map(|all| all.iter().count());
+            output.0.push(format!("Option<All>: {count:?}"));
+        }
+    }
+
+    schedule.run(&mut world);
+
+    assert_eq!(
+        world.resource::<Output>().0,
+        &[
+            "MaybeAll: []",
+            r#"MaybeAll: ["Henry"]"#,
+            r#"MaybeAll: ["Eliza", "Reginald"]"#,
+            "Option<One>: None",
+            r#"Option<One>: Some("Henry")"#,
+            "Option<One>: None",
+            "Option<All>: None",
+            "Option<All>: Some(1)",
+            "Option<All>: Some(2)",
+        ]
+    );
+}
+
+// An entity with only a `Human` would match both of these queries,
+// so they must be reported as conflicting.
+#[test]
+#[should_panic(expected = "conflicts with a previous system parameter")]
+fn all_access_conflict() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Person, Human>()
+        .register_component_as::<dyn Person, Dolphin>();
+
+    fn conflicting(_q1: Query<&dyn Person>, _q2: Query<&mut Human, Without<Dolphin>>) {}
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(conflicting);
+    schedule.run(&mut world);
+}
+
+#[test]
+fn has_and_count() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    world.spawn_empty();
+    world.spawn(RecA(vec![]));
+    world.spawn(RecB(vec![]));
+    world.spawn((RecA(vec![]), RecB(vec![])));
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(print_counts);
+
+    // This would conflict with `HasTrait` and `TraitCount` if they accessed any components.
+    fn print_counts(
+        q: Query<(HasTrait<dyn Messages>, TraitCount<dyn Messages>)>,
+        _q2: Query<&mut dyn Messages>,
+        mut output: ResMut<Output>,
+    ) {
+        for (has, count) in &q {
+            output.0.push(format!("{has}: {count}"));
+        }
+    }
+
+    schedule.run(&mut world);
+
+    assert_eq!(
+        world.resource::<Output>().0,
+        &["false: 0", "true: 1", "true: 1", "true: 2"]
+    );
+}
+
+#[test]
+fn downcast() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    world.spawn(RecA(vec!["a".to_owned()]));
+    world.spawn((RecA(vec![]), RecB(vec!["b".to_owned()])));
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems((send_to_b, print_downcast).chain());
+
+    fn send_to_b(mut q: Query<&mut dyn Messages>) {
+        for mut messages in &mut q {
+            if let Some(mut rec_b) = messages.get_mut::<RecB>() {
+                rec_b.0.push("c".to_owned());
+            }
+        }
+    }
+
+    fn print_downcast(q: Query<&dyn Messages>, mut output: ResMut<Output>) {
+        for messages in &q {
+            let rec_a = messages.get::<RecA>().map(|rec| rec.0.len());
+            let rec_b = messages.get::<RecB>().map(|rec| rec.0.len());
+            output.0.push(format!("{rec_a:?} {rec_b:?}"));
+            for (info, _) in messages.iter_with_info() {
+                let short_name = info.type_name().rsplit("::").next().unwrap();
+                output.0.push(format!("  {short_name}"));
+            }
+        }
+    }
+
+    schedule.run(&mut world);
+
+    assert_eq!(
+        world.resource::<Output>().0,
+        &[
+            "Some(1) None",
+            "  RecA",
+            "Some(0) Some(2)",
+            "  RecA",
+            "  RecB"
+        ]
+    );
+
+    // Components that were never registered with the trait can't be downcast to.
+    let entity = world
+        .spawn((RecA(vec![]), Human("Garbanzo".to_owned(), 7)))
+        .id();
+    let mut state = world.query::<&dyn Messages>();
+    let messages = state.get(&world, entity).unwrap();
+    assert!(messages.get::<Human>().is_none());
+    let ids: Vec<_> = messages.iter_with_ids().map(|(id, _)| id).collect();
+    assert_eq!(ids, [world.component_id::<RecA>().unwrap()]);
+}
+
+#[queryable]
+pub trait Numbered {
+    fn number(&self) -> usize;
+}
+
+macro_rules! numbered_components {
+    ($($name:ident),*) => {
+        $(
+            #[derive(Component)]
+            pub struct $name(usize);
+
+            impl Numbered for $name {
+                fn number(&self) -> usize {
+                    self.0
+                }
+            }
+        )*
+
+        fn register_numbered(world: &mut World) {
+            $(world.register_component_as::<dyn Numbered, $name>();)*
+        }
+    };
+}
+
+numbered_components!(
+    N0, N1, N2, N3, N4, N5, N6, N7, N8, N9, N10, N11, N12, N13, N14, N15, N16, N17, N18, N19, N20,
+    N21, N22, N23, N24, N25, N26, N27, N28, N29, N30, N31, N32, N33, N34, N35, N36, N37, N38, N39,
+    N40, N41, N42, N43, N44, N45, N46, N47, N48, N49, N50, N51, N52, N53, N54, N55, N56, N57, N58,
+    N59, N60, N61, N62, N63, N64, N65, N66, N67, N68, N69
+);
+
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+pub struct SparseNumbered(usize);
+
+impl Numbered for SparseNumbered {
+    fn number(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+pub struct OtherSparseNumbered(usize);
+
+impl Numbered for OtherSparseNumbered {
+    fn number(&self) -> usize {
+        self.0
+    }
+}
+
+// Make sure we still find every impl when there are too many to cache.
+#[test]
+fn many_impls() {
+    let mut world = World::new();
+    register_numbered(&mut world);
+    world.register_component_as::<dyn Numbered, SparseNumbered>();
+
+    world.spawn((N3(3), N63(63), N64(64), N69(69)));
+    world.spawn((N0(0), SparseNumbered(100)));
+    world.spawn(N68(68));
+
+    let mut state = world.query::<&dyn Numbered>();
+    let numbers: Vec<Vec<usize>> = state
+        .iter(&world)
+        .map(|all| all.iter().map(|x| x.number()).collect())
+        .collect();
+    assert_eq!(numbers, [vec![3, 63, 64, 69], vec![0, 100], vec![68]]);
+}
+
+// Archetypes with more impls than can be cached get every registered impl checked for each entity.
+#[test]
+fn uncached_impls() {
+    let mut world = World::new();
+    register_numbered(&mut world);
+
+    world.spawn(N64(64));
+    world.spawn((N65(65), N69(69)));
+    world.spawn((N0(0), N33(33), N66(66)));
+    world.spawn((N1(1), N2(2), N3(3), N68(68)));
+
+    let expected = [vec![64], vec![65, 69], vec![0, 33, 66], vec![1, 2, 3, 68]];
+
+    let mut state = world.query::<&dyn Numbered>();
+    let numbers: Vec<Vec<usize>> = state
+        .iter(&world)
+        .map(|all| all.iter().map(|x| x.number()).collect())
+        .collect();
+    assert_eq!(numbers, expected);
+
+    let mut state = world.query::<&mut dyn Numbered>();
+    let numbers: Vec<Vec<usize>> = state
+        .iter_mut(&mut world)
+        .map(|all| all.into_iter().map(|x| x.number()).collect())
+        .collect();
+    assert_eq!(numbers, expected);
+
+    let mut state = world.query::<DenseAll<&dyn Numbered>>();
+    let numbers: Vec<Vec<usize>> = state
+        .iter(&world)
+        .map(|all| all.iter().map(|x| x.number()).collect())
+        .collect();
+    assert_eq!(numbers, expected);
+}
+
+#[test]
+fn dense_queries() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Person, Human>()
+        .register_component_as::<dyn Person, Dolphin>();
+
+    world.spawn(Human("Henry".to_owned(), 22));
+    world.spawn((Human("Eliza".to_owned(), 31), Fem));
+    world.spawn(Dolphin(27));
+    world.spawn((Human("Garbanzo".to_owned(), 7), Dolphin(4)));
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems((age_up_dense, print_dense).chain());
+
+    fn age_up_dense(mut q: Query<DenseOne<&mut dyn Person>>) {
+        for mut person in &mut q {
+            let age = person.age();
+            person.set_age(age + 1);
+        }
+    }
+
+    fn print_dense(
+        one: Query<DenseOne<&dyn Person>, Without<Fem>>,
+        all: Query<DenseAll<&dyn Person>>,
+        mut output: ResMut<Output>,
+    ) {
+        for person in &one {
+            output
+                .0
+                .push(format!("{}: {}", person.name(), person.age()));
+        }
+        for people in &all {
+            let ages: Vec<_> = people.iter().map(|p| p.age()).collect();
+            output.0.push(format!("{ages:?}"));
+        }
+    }
+
+    schedule.run(&mut world);
+
+    assert_eq!(
+        world.resource::<Output>().0,
+        &[
+            "Henry: 23",
+            "Reginald: 28",
+            "[23]",
+            "[32]",
+            "[28]",
+            "[7, 4]",
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Dense trait queries require every component implementing")]
+fn dense_sparse_impl() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    world.query::<DenseAll<&dyn Messages>>();
+}
+
+#[test]
+fn late_registration() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Person, Human>()
+        .allow_late_registration::<dyn Person>();
+
+    world.spawn(Human("Henry".to_owned(), 22));
+    world.spawn(Dolphin(27));
+
+    let mut old_all = world.query::<&dyn Person>();
+    let mut old_one = world.query::<One<&dyn Person>>();
+
+    world.register_component_as::<dyn Person, Dolphin>();
+    let mut new_all = world.query::<&dyn Person>();
+
+    // Existing queries don't have access to the new impl, so they keep ignoring it.
+    let names = |people: &[&dyn Person]| {
+        people
+            .iter()
+            .map(|p| p.name().to_owned())
+            .collect::<Vec<_>>()
+    };
+    let old: Vec<_> = old_all.iter(&world).flatten().collect();
+    assert_eq!(names(&old), ["Henry"]);
+    let old: Vec<_> = old_one.iter(&world).collect();
+    assert_eq!(names(&old), ["Henry"]);
+    let new: Vec<_> = new_all.iter(&world).flatten().collect();
+    assert_eq!(names(&new), ["Henry", "Reginald"]);
+}
+
+#[test]
+fn refresh_after_late_registration() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Person, Human>()
+        .allow_late_registration::<dyn Person>();
+
+    world.spawn(Human("Henry".to_owned(), 22));
+    world.spawn(Dolphin(27));
+
+    let generation = |world: &World| world.trait_registry::<dyn Person>().unwrap().generation();
+    let mut state = world.query::<&dyn Person>();
+    let mut cached = generation(&world);
+
+    world.register_component_as::<dyn Person, Dolphin>();
+    assert_ne!(generation(&world), cached);
+    // The old query state gets told that its impls are outdated, so it can warn about them.
+    let registry = world.resource::<TraitImplRegistry<dyn Person>>();
+    assert!(registry.retired[0].outdated.load(Ordering::Relaxed));
+    assert!(!registry.lists.outdated.load(Ordering::Relaxed));
+
+    // Creating the query state again picks up the new impl.
+    if generation(&world) != cached {
+        cached = generation(&world);
+        state = world.query();
+    }
+    let names: Vec<_> = state
+        .iter(&world)
+        .flatten()
+        .map(|p| p.name().to_owned())
+        .collect();
+    assert_eq!(names, ["Henry", "Reginald"]);
+
+    // Only the lists that are still used by a query state are kept around.
+    world.unregister_component_as::<dyn Person, Dolphin>();
+    assert_ne!(generation(&world), cached);
+    let registry = world.resource::<TraitImplRegistry<dyn Person>>();
+    assert_eq!(registry.retired.len(), 1);
+    drop(state);
+    world.register_component_as::<dyn Person, Dolphin>();
+    let registry = world.resource::<TraitImplRegistry<dyn Person>>();
+    assert!(registry.retired.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Cannot register new trait impls after the game has started")]
+fn sealed_registration() {
+    let mut world = World::new();
+    world.register_component_as::<dyn Person, Human>();
+    world.query::<&dyn Person>();
+    world.register_component_as::<dyn Person, Dolphin>();
+}
+
+#[test]
+fn try_register() {
+    let mut world = World::new();
+    assert_eq!(
+        world.try_register_component_as::<dyn Person, Human>(),
+        Ok(RegistrationOutcome::Registered)
+    );
+    assert_eq!(
+        world.try_register_component_as::<dyn Person, Human>(),
+        Ok(RegistrationOutcome::AlreadyRegistered)
+    );
+
+    world.query::<&dyn Person>();
+    assert_eq!(
+        world.try_register_component_as::<dyn Person, Dolphin>(),
+        Err(TraitRegistrationError::Sealed)
+    );
+    // Registering an existing impl is still fine.
+    assert_eq!(
+        world.try_register_component_as::<dyn Person, Human>(),
+        Ok(RegistrationOutcome::AlreadyRegistered)
+    );
+
+    world.allow_late_registration::<dyn Person>();
+    assert_eq!(
+        world.try_register_component_as::<dyn Person, Dolphin>(),
+        Ok(RegistrationOutcome::Registered)
+    );
+}
+
+#[test]
+fn registry_introspection() {
+    let mut world = World::new();
+    assert!(world.trait_registry::<dyn Messages>().is_none());
+
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    let registry = world.trait_registry::<dyn Messages>().unwrap();
+    assert_eq!(registry.len(), 2);
+    assert!(!registry.is_sealed());
+    assert_eq!(
+        registry.component_ids(),
+        [
+            world.component_id::<RecA>().unwrap(),
+            world.component_id::<RecB>().unwrap()
+        ]
+    );
+    let storage: Vec<_> = registry
+        .iter()
+        .map(|info| (info.type_name(), info.storage_type(), info.size_bytes()))
+        .collect();
+    assert_eq!(
+        storage,
+        [
+            (
+                std::any::type_name::<RecA>(),
+                StorageType::Table,
+                std::mem::size_of::<RecA>()
+            ),
+            (
+                std::any::type_name::<RecB>(),
+                StorageType::SparseSet,
+                std::mem::size_of::<RecB>()
+            ),
+        ]
+    );
+    let human = world.init_component::<Human>();
+    assert!(world
+        .trait_registry::<dyn Messages>()
+        .unwrap()
+        .get(human)
+        .is_none());
+
+    world.query::<&dyn Messages>();
+    assert!(world.trait_registry::<dyn Messages>().unwrap().is_sealed());
+}
+
+#[test]
+fn unregister() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    world.spawn((RecA(vec!["a".to_owned()]), RecB(vec!["b".to_owned()])));
+
+    let mut old = world.query::<&dyn Messages>();
+    world.unregister_component_as::<dyn Messages, RecA>();
+    let mut new = world.query::<&dyn Messages>();
+
+    let registry = world.trait_registry::<dyn Messages>().unwrap();
+    assert_eq!(
+        registry.component_ids(),
+        [world.component_id::<RecB>().unwrap()]
+    );
+
+    let read = |state: &mut QueryState<&dyn Messages>, world: &World| {
+        state
+            .iter(world)
+            .flatten()
+            .flat_map(|m| m.read().to_vec())
+            .collect::<Vec<_>>()
+    };
+    // Queries created before the component was unregistered keep fetching it.
+    assert_eq!(read(&mut old, &world), ["a", "b"]);
+    assert_eq!(read(&mut new, &world), ["b"]);
+
+    world.reset_trait_registry::<dyn Messages>();
+    assert!(world.trait_registry::<dyn Messages>().unwrap().is_empty());
+    // The registry is no longer sealed, so we can register components again.
+    world.register_component_as::<dyn Messages, RecA>();
+    let mut newest = world.query::<&dyn Messages>();
+
+    assert_eq!(read(&mut old, &world), ["a", "b"]);
+    assert_eq!(read(&mut new, &world), ["b"]);
+    assert_eq!(read(&mut newest, &world), ["a"]);
+}
+
+#[test]
+fn registration_priority() {
+    let mut world = World::new();
+    world
+        .register_component_as_with_priority::<dyn Numbered, N1>(0)
+        .register_component_as_with_priority::<dyn Numbered, SparseNumbered>(5)
+        .register_component_as_with_priority::<dyn Numbered, N2>(5)
+        .register_component_as_with_priority::<dyn Numbered, N3>(-1)
+        .allow_late_registration::<dyn Numbered>();
+
+    world.spawn((N1(1), N2(2), N3(3), N4(4), SparseNumbered(100)));
+
+    let numbers = |state: &mut QueryState<&dyn Numbered>, world: &World| {
+        state
+            .iter(world)
+            .flat_map(|all| all.iter().map(|x| x.number()).collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+    };
+    // Higher priorities come first, regardless of storage type.
+    let mut old = world.query::<&dyn Numbered>();
+    assert_eq!(numbers(&mut old, &world), [100, 2, 1, 3]);
+
+    let mut state = world.query::<&mut dyn Numbered>();
+    let mutable: Vec<_> = state
+        .iter_mut(&mut world)
+        .flat_map(|all| all.into_iter().map(|x| x.number()).collect::<Vec<_>>())
+        .collect();
+    assert_eq!(mutable, [100, 2, 1, 3]);
+
+    // Inserting an impl in the middle of the order doesn't affect existing queries.
+    world.register_component_as_with_priority::<dyn Numbered, N4>(10);
+    let mut new = world.query::<&dyn Numbered>();
+    assert_eq!(numbers(&mut old, &world), [100, 2, 1, 3]);
+    assert_eq!(numbers(&mut new, &world), [4, 100, 2, 1, 3]);
+}
+
+#[test]
+fn first() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as_with_priority::<dyn Messages, RecB>(1);
+
+    world.spawn(RecA(vec!["a".to_owned()]));
+    world.spawn(RecB(vec!["b".to_owned()]));
+    world.spawn((
+        RecA(vec!["both a".to_owned()]),
+        RecB(vec!["both b".to_owned()]),
+    ));
+    world.spawn(Human("Garbanzo".to_owned(), 7));
+
+    // `One` skips entities with several impls, but `FirstImpl` picks the one with the highest priority.
+    let mut one = world.query::<One<&dyn Messages>>();
+    let read: Vec<_> = one.iter(&world).flat_map(|m| m.read().to_vec()).collect();
+    assert_eq!(read, ["a", "b"]);
+
+    let mut first = world.query::<FirstImpl<&mut dyn Messages>>();
+    for mut messages in first.iter_mut(&mut world) {
+        messages.send(&"hi");
+    }
+
+    let mut first = world.query::<FirstImpl<&dyn Messages>>();
+    let read: Vec<_> = first.iter(&world).flat_map(|m| m.read().to_vec()).collect();
+    assert_eq!(
+        read,
+        ["a", "RecA: hi", "b", "RecB: hi", "both b", "RecB: hi"]
+    );
+}
+
+// The sparse set of a higher priority impl exists even in archetypes that don't contain it.
+#[test]
+fn one_sparse_priority() {
+    let mut world = World::new();
+    world
+        .register_component_as_with_priority::<dyn Numbered, SparseNumbered>(1)
+        .register_component_as::<dyn Numbered, OtherSparseNumbered>();
+
+    world.spawn(SparseNumbered(1));
+    world.spawn(OtherSparseNumbered(2));
+
+    let mut one = world.query::<One<&mut dyn Numbered>>();
+    let numbers: Vec<_> = one.iter_mut(&mut world).map(|x| x.number()).collect();
+    assert_eq!(numbers, [1, 2]);
+
+    let mut one = world.query::<One<&dyn Numbered>>();
+    let numbers: Vec<_> = one.iter(&world).map(|x| x.number()).collect();
+    assert_eq!(numbers, [1, 2]);
+
+    let mut first = world.query::<FirstImpl<&dyn Numbered>>();
+    let numbers: Vec<_> = first.iter(&world).map(|x| x.number()).collect();
+    assert_eq!(numbers, [1, 2]);
+}
+
+/// Collects the messages of the warnings that get logged on this thread while running `f`.
+fn capture_warnings(f: impl FnOnce()) -> Vec<String> {
+    use bevy::utils::tracing::{field::Field, span, Event, Level, Metadata, Subscriber};
+
+    struct Warnings(Arc<Mutex<Vec<String>>>);
+
+    impl Subscriber for Warnings {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            *metadata.level() == Level::WARN
+        }
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+        fn event(&self, event: &Event<'_>) {
+            let mut message = String::new();
+            event.record(&mut |field: &Field, value: &dyn Debug| {
+                if field.name() == "message" {
+                    message = format!("{value:?}");
+                }
+            });
+            self.0.lock().unwrap().push(message);
+        }
+        fn enter(&self, _: &span::Id) {}
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    let warnings = Arc::new(Mutex::new(vec![]));
+    bevy::utils::tracing::subscriber::with_default(Warnings(warnings.clone()), f);
+    let warnings = warnings.lock().unwrap();
+    warnings.clone()
+}
+
+#[test]
+fn one_diagnostics() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>()
+        .warn_on_multiple_impls::<dyn Messages>();
+
+    world.spawn(RecA(vec![]));
+    let both = world.spawn((RecA(vec![]), RecB(vec![]))).id();
+    let both_human =
+        (world.spawn((RecA(vec![]), RecB(vec![]), Human("Garbanzo".to_owned(), 7)))).id();
+
+    let mut state = None;
+    let warnings = capture_warnings(|| state = Some(world.query::<One<&dyn Messages>>()));
+    let mut state = state.unwrap();
+    // Both archetypes have the same impls, so they only get reported once.
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("RecA") && warnings[0].contains("RecB"));
+
+    // The same impls don't get reported again, even by other queries or for new archetypes.
+    let warnings = capture_warnings(|| {
+        world.spawn((RecA(vec![]), RecB(vec![]), Fem));
+        assert_eq!(state.iter(&world).count(), 1);
+        let mut other = world.query::<One<&dyn Messages>>();
+        assert_eq!(other.iter(&world).count(), 1);
+    });
+    assert!(warnings.is_empty());
+
+    let state = <One<&dyn Messages> as WorldQuery>::init_state(&mut world);
+    let skipped = state.skipped_archetypes(&world);
+    let expected = [both, both_human].map(|e| world.entity(e).archetype().id());
+    assert_eq!(skipped[..2], expected);
+    assert_eq!(skipped.len(), 3);
+}
+
+// Queries only skip archetypes based on the impls they know about.
+#[test]
+fn skipped_archetypes_after_late_registration() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .allow_late_registration::<dyn Messages>();
+
+    world.spawn((RecA(vec![]), RecB(vec![])));
+
+    let old = <One<&dyn Messages> as WorldQuery>::init_state(&mut world);
+    let mut old_query = world.query::<One<&dyn Messages>>();
+    world.register_component_as::<dyn Messages, RecB>();
+    let new = <One<&dyn Messages> as WorldQuery>::init_state(&mut world);
+    let mut new_query = world.query::<One<&dyn Messages>>();
+
+    assert!(old.skipped_archetypes(&world).is_empty());
+    assert_eq!(old_query.iter(&world).count(), 1);
+    assert_eq!(new.skipped_archetypes(&world).len(), 1);
+    assert_eq!(new_query.iter(&world).count(), 0);
+}
+
+// Archetypes skipped because of another trait in the query's filters aren't reported.
+#[test]
+fn skipped_archetypes_with_filters() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>()
+        .register_component_as::<dyn Person, Human>()
+        .register_component_as::<dyn Person, Dolphin>();
+
+    world.spawn((RecA(vec![]), Human("Garbanzo".to_owned(), 7), Dolphin(27)));
+    let both = world.spawn((RecA(vec![]), RecB(vec![]))).id();
+    world.spawn((RecA(vec![]), Human("Garbanzo".to_owned(), 7)));
+
+    let mut query = world.query_filtered::<One<&dyn Messages>, WithOneTrait<dyn Person>>();
+    assert_eq!(query.iter(&world).count(), 1);
+
+    let state = <One<&dyn Messages> as WorldQuery>::init_state(&mut world);
+    let skipped = state.skipped_archetypes(&world);
+    assert_eq!(skipped, [world.entity(both).archetype().id()]);
+}
+
+#[test]
+fn exclusive_remove_older() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>()
+        .validate_exclusive_trait::<dyn Messages>(ExclusivePolicy::RemoveOlder);
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(validate_exclusive_trait::<dyn Messages>);
+
+    let older = world.spawn(RecA(vec![])).id();
+    let both = world.spawn((RecA(vec![]), RecB(vec![]))).id();
+    schedule.run(&mut world);
+
+    // Impls added at the same time are resolved by priority.
+    assert!(world.entity(both).contains::<RecA>());
+    assert!(!world.entity(both).contains::<RecB>());
+
+    world.entity_mut(older).insert(RecB(vec![]));
+    schedule.run(&mut world);
+
+    assert!(!world.entity(older).contains::<RecA>());
+    assert!(world.entity(older).contains::<RecB>());
+}
+
+// Queries that run before the validation system can still see several impls.
+#[test]
+fn exclusive_validated_in_post_update() {
+    let mut app = App::new();
+    app.init_resource::<Output>()
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>()
+        .validate_exclusive_trait::<dyn Messages>(ExclusivePolicy::RemoveOlder)
+        .add_systems(Update, count_impls);
+
+    fn count_impls(q: Query<&dyn Messages>, mut output: ResMut<Output>) {
+        for all in &q {
+            output.0.push(format!("{}", all.iter().count()));
+        }
+    }
+
+    let entity = app.world.spawn((RecA(vec![]), RecB(vec![]))).id();
+    app.update();
+    app.update();
+
+    assert_eq!(app.world.resource::<Output>().0, ["2", "1"]);
+    assert!(!app.world.entity(entity).contains::<RecB>());
+}
+
+#[test]
+#[should_panic(expected = "has multiple components implementing the exclusive trait")]
+fn exclusive_panic() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>()
+        .validate_exclusive_trait::<dyn Messages>(ExclusivePolicy::Panic);
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(validate_exclusive_trait::<dyn Messages>);
+
+    world.spawn(RecA(vec![]));
+    schedule.run(&mut world);
+    world.spawn((RecA(vec![]), RecB(vec![])));
+    schedule.run(&mut world);
+}
+
+#[test]
+fn removed_trait_components() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    let a = world.spawn(RecA(vec![])).id();
+    let b = world.spawn((RecA(vec![]), RecB(vec![]))).id();
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(print_removed);
+
+    fn print_removed(
+        mut removed: RemovedTraitComponents<dyn Messages>,
+        mut output: ResMut<Output>,
+    ) {
+        for (entity, component) in removed.iter() {
+            output.0.push(format!("{entity:?} {component:?}"));
+        }
+    }
+
+    schedule.run(&mut world);
+    world.entity_mut(a).remove::<RecA>();
+    world.entity_mut(b).remove::<RecB>();
+    world.despawn(b);
+    schedule.run(&mut world);
+    // Removals are only reported once.
+    schedule.run(&mut world);
+
+    let rec_a = world.component_id::<RecA>().unwrap();
+    let rec_b = world.component_id::<RecB>().unwrap();
+    assert_eq!(
+        world.resource::<Output>().0,
+        [
+            format!("{a:?} {rec_a:?}"),
+            format!("{b:?} {rec_a:?}"),
+            format!("{b:?} {rec_b:?}"),
+        ]
+    );
+}
+
+#[test]
+fn removed_trait_components_len() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    let entity = world.spawn((RecA(vec![]), RecB(vec![]))).id();
+    // These are removed before the system has created any readers.
+    world.entity_mut(entity).remove::<(RecA, RecB)>();
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(print_len);
+
+    fn print_len(removed: RemovedTraitComponents<dyn Messages>, mut output: ResMut<Output>) {
+        output
+            .0
+            .push(format!("{} {}", removed.len(), removed.is_empty()));
+    }
+
+    // `len` doesn't consume the removals.
+    schedule.run(&mut world);
+    schedule.run(&mut world);
+
+    assert_eq!(world.resource::<Output>().0, ["2 false", "2 false"]);
+}
+
+#[test]
+fn run_conditions() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world.register_component_as::<dyn Person, Human>();
+
+    fn print(name: &'static str) -> impl FnMut(ResMut<Output>) {
+        move |mut output: ResMut<Output>| output.0.push(name.to_owned())
+    }
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(
+        (
+            print("with").run_if(any_with_trait::<dyn Person>()),
+            print("changed").run_if(any_trait_changed::<dyn Person>()),
+            print("added").run_if(any_trait_added::<dyn Person>()),
+            print(""),
+        )
+            .chain(),
+    );
+
+    schedule.run(&mut world);
+    let entity = world.spawn(Human("Garbanzo".to_owned(), 7)).id();
+    schedule.run(&mut world);
+    schedule.run(&mut world);
+    world.get_mut::<Human>(entity).unwrap().1 += 1;
+    schedule.run(&mut world);
+
+    assert_eq!(
+        world.resource::<Output>().0,
+        ["", "with", "changed", "added", "", "with", "", "with", "changed", ""]
+    );
+}
+
+#[test]
+fn entity_accessors() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    let one = world.spawn(RecA(vec!["a".to_owned()])).id();
+    let both = world
+        .spawn((RecA(vec!["a".to_owned()]), RecB(vec!["b".to_owned()])))
+        .id();
+    let none = world.spawn(Human("Garbanzo".to_owned(), 7)).id();
+
+    let read = |traits: ReadTraits<dyn Messages>| {
+        traits
+            .iter()
+            .flat_map(|m| m.read().to_vec())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(read(world.get_traits(both).unwrap()), ["a", "b"]);
+    assert!(world.get_traits::<dyn Messages>(none).is_none());
+
+    let entity = world.entity(one);
+    assert_eq!(
+        entity.get_one_trait::<dyn Messages>().unwrap().read(),
+        ["a"]
+    );
+    assert!(world.entity(both).get_one_trait::<dyn Messages>().is_none());
+
+    for mut messages in world.get_traits_mut::<dyn Messages>(both).unwrap() {
+        messages.send(&"hello");
+    }
+    let mut entity = world.entity_mut(one);
+    for mut messages in entity.get_traits_mut::<dyn Messages>().unwrap() {
+        messages.send(&"hi");
+    }
+    assert_eq!(read(entity.get_traits().unwrap()), ["a", "RecA: hi"]);
+    assert_eq!(
+        read(world.entity(both).get_traits().unwrap()),
+        ["a", "RecA: hello", "b", "RecB: hello"]
+    );
+}
+
+#[test]
+fn remove_traits() {
+    let mut world = World::new();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    let a = world
+        .spawn((RecA(vec![]), RecB(vec![]), Human("Garbanzo".to_owned(), 7)))
+        .id();
+    let b = world.spawn((RecA(vec![]), RecB(vec![]), Dolphin(27))).id();
+
+    world.entity_mut(a).remove_traits::<dyn Messages>();
+    assert!(!world.entity(a).contains::<RecA>());
+    assert!(!world.entity(a).contains::<RecB>());
+    assert!(world.entity(a).contains::<Human>());
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(remove_messages);
+
+    fn remove_messages(mut commands: Commands, q: Query<Entity, With<Dolphin>>) {
+        for entity in &q {
+            commands.entity(entity).remove_traits::<dyn Messages>();
+        }
+    }
+
+    schedule.run(&mut world);
+    assert!(world.get_traits::<dyn Messages>(b).is_none());
+    assert!(world.entity(b).contains::<Dolphin>());
+}
+
+#[test]
+fn modify_traits() {
+    let mut world = World::new();
+    world.init_resource::<Output>();
+    world
+        .register_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    let both = world.spawn((RecA(vec![]), RecB(vec![]))).id();
+    let one = world.spawn(RecA(vec![])).id();
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems((send, apply_deferred, print_changed).chain());
+
+    fn send(mut commands: Commands, q: Query<Entity, WithTrait<dyn Messages>>) {
+        for entity in &q {
+            commands
+                .entity(entity)
+                .modify_traits::<dyn Messages>(|mut m| m.send(&"all"))
+                .modify_one_trait::<dyn Messages>(|mut m| m.send(&"one"));
+        }
+    }
+
+    fn print_changed(q: Query<(Entity, ChangedAll<&dyn Messages>)>, mut output: ResMut<Output>) {
+        for (entity, changed) in &q {
+            for messages in changed {
+                output.0.push(format!("{entity:?}: {:?}", messages.read()));
+            }
+        }
+    }
+
+    schedule.run(&mut world);
+    world.resource_mut::<Output>().0.clear();
+    schedule.run(&mut world);
+
+    assert_eq!(
+        world.resource::<Output>().0,
+        [
+            format!("{both:?}: {:?}", ["RecA: all", "RecA: all"]),
+            format!("{both:?}: {:?}", ["RecB: all", "RecB: all"]),
+            format!(
+                "{one:?}: {:?}",
+                ["RecA: all", "RecA: one", "RecA: all", "RecA: one"]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn clone_traits() {
+    let mut world = World::new();
+    world
+        .register_cloneable_component_as::<dyn Messages, RecA>()
+        .register_component_as::<dyn Messages, RecB>();
+
+    let source = world
+        .spawn((RecA(vec!["a".to_owned()]), RecB(vec!["b".to_owned()])))
+        .id();
+    let target = world.spawn_empty().id();
+    let target2 = world.spawn(Human("Garbanzo".to_owned(), 7)).id();
+
+    // Only cloneable impls get copied.
+    world.clone_traits::<dyn Messages>(source, target);
+    assert_eq!(world.get::<RecA>(target).unwrap().0, ["a"]);
+    assert!(world.get::<RecB>(target).is_none());
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(move |mut commands: Commands| {
+        commands.clone_traits::<dyn Messages>(source, target2);
+    });
+    schedule.run(&mut world);
+    assert_eq!(world.get::<RecA>(target2).unwrap().0, ["a"]);
+    assert!(world.get::<Human>(target2).is_some());
+    // The source entity is unchanged.
+    assert!(world.get::<RecA>(source).is_some());
+    assert!(world.get::<RecB>(source).is_some());
+}
+
+#[queryable(reflect)]
+pub trait Labeled {
+    fn label(&self) -> &str;
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Labeled)]
+pub struct LabelA;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Labeled)]
+#[component(storage = "SparseSet")]
+pub struct LabelB;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Unlabeled;
+
+impl Labeled for LabelA {
+    fn label(&self) -> &str {
+        "A"
+    }
+}
+
+impl Labeled for LabelB {
+    fn label(&self) -> &str {
+        "B"
+    }
+}
+
+impl Labeled for Unlabeled {
+    fn label(&self) -> &str {
+        "unlabeled"
+    }
+}
+
+#[test]
+fn register_from_type_registry() {
+    let mut world = World::new();
+    world.init_resource::<AppTypeRegistry>();
+    {
+        let mut registry = world.resource::<AppTypeRegistry>().write();
+        registry.register::<LabelA>();
+        registry.register::<LabelB>();
+        registry.register::<Unlabeled>();
+    }
+    world.register_traits_from_type_registry::<dyn Labeled>();
+
+    world.spawn((LabelA, LabelB, Unlabeled));
+
+    let mut state = world.query::<&dyn Labeled>();
+    let mut labels: Vec<_> = state.iter(&world).flatten().map(|l| l.label()).collect();
+    // The type registry doesn't have a stable iteration order.
+    labels.sort();
+    assert_eq!(labels, ["A", "B"]);
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct ReflectCounter(u32);
+
+#[test]
+fn query_reflect_components() {
+    let mut world = World::new();
+    world.init_resource::<AppTypeRegistry>();
+    {
+        let mut registry = world.resource::<AppTypeRegistry>().write();
+        registry.register::<LabelA>();
+        registry.register::<LabelB>();
+        registry.register::<Unlabeled>();
+        registry.register::<ReflectCounter>();
+    }
+
+    let a = world.spawn((LabelA, LabelB, ReflectCounter(1))).id();
+    let b = world.spawn(ReflectCounter(5)).id();
+    // `Unlabeled` has not been initialized, so it can't be registered.
+    world.register_reflect_components();
+
+    // Components are registered in order of their type names.
+    let type_names = |world: &World, entity| {
+        world
+            .get_traits::<dyn Reflect>(entity)
+            .into_iter()
+            .flatten()
+            .map(|component| {
+                component
+                    .type_name()
+                    .rsplit("::")
+                    .next()
+                    .unwrap()
+                    .to_owned()
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        type_names(&world, a),
+        ["LabelA", "LabelB", "ReflectCounter"]
+    );
+    assert_eq!(type_names(&world, b), ["ReflectCounter"]);
+
+    let mut state = world.query::<All<&mut dyn Reflect>>();
+    for components in state.iter_mut(&mut world) {
+        for mut component in components {
+            if let Some(counter) = component.downcast_mut::<ReflectCounter>() {
+                counter.0 += 1;
+            }
+        }
+    }
+    assert_eq!(world.get::<ReflectCounter>(a).unwrap().0, 2);
+    assert_eq!(world.get::<ReflectCounter>(b).unwrap().0, 6);
+
+    let mut state = world.query::<One<&dyn Reflect>>();
+    let counters: Vec<_> = state
+        .iter(&world)
+        .filter_map(|component| component.downcast_ref::<ReflectCounter>())
+        .map(|counter| counter.0)
+        .collect();
+    assert_eq!(counters, [6]);
+
+    world.entity_mut(a).remove_traits::<dyn Reflect>();
+    assert!(world.get_traits::<dyn Reflect>(a).is_none());
+    assert!(world.get::<LabelB>(a).is_none());
+
+    // Components initialized later can be registered once late registration is allowed.
+    world.allow_late_registration::<dyn Reflect>();
+    let c = world.spawn((Unlabeled, ReflectCounter(0))).id();
+    world.register_reflect_components();
+    assert_eq!(type_names(&world, c), ["ReflectCounter", "Unlabeled"]);
+    let registry = world.resource::<TraitImplRegistry<dyn Reflect>>();
+    assert_eq!(registry.lists.type_data.len(), 4);
+
+    // The type data of unregistered components is dropped along with them.
+    world.unregister_component_as::<dyn Reflect, Unlabeled>();
+    let registry = world.resource::<TraitImplRegistry<dyn Reflect>>();
+    assert_eq!(registry.lists.type_data.len(), 3);
+}